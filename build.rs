@@ -0,0 +1,59 @@
+/// Generates the in-process solution registry used by the `all`, `time` and `stress` commands.
+/// Every `src/bin/YYYY-DD.rs` is included as a module of the `registry` binary and its `SOLUTION` constant,
+/// emitted by the `solution!` macro, is collected into a table.
+///
+/// Solutions listed in `AOC_FAILED_TO_COMPILE`, e.g. `2024-06,2024-09`, are left out of the table and listed in
+/// `FAILED_TO_COMPILE` instead. The `advent_of_code` binary sets it when the `registry` binary doesn't compile.
+use std::{env, fs, path::Path};
+
+// shared with the `advent_of_code` binary, which sets `AOC_FAILED_TO_COMPILE`.
+#[path = "src/template/solution_bins.rs"]
+mod solution_bins;
+
+use solution_bins::{solution_bins, FAILED_TO_COMPILE_ENV};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+    println!("cargo:rerun-if-changed=src/template/solution_bins.rs");
+    println!("cargo:rerun-if-env-changed={FAILED_TO_COMPILE_ENV}");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+    let puzzles = solution_bins(&bin_dir);
+
+    let failed_to_compile = env::var(FAILED_TO_COMPILE_ENV).unwrap_or_default();
+    let (failed, puzzles): (Vec<String>, Vec<String>) = puzzles
+        .into_iter()
+        .partition(|puzzle| failed_to_compile.split(',').any(|x| x == puzzle));
+
+    let mut registry = String::new();
+
+    for puzzle in &puzzles {
+        let path = bin_dir.join(format!("{puzzle}.rs"));
+        let module = puzzle.replace('-', "_");
+        // the `main` of a solution is unused as a module.
+        registry.push_str(&format!(
+            "#[path = {path:?}]\n#[allow(dead_code)]\nmod day_{module};\n\n"
+        ));
+    }
+
+    registry.push_str("pub const SOLUTIONS: &[advent_of_code::template::Solution] = &[\n");
+    for puzzle in &puzzles {
+        let module = puzzle.replace('-', "_");
+        registry.push_str(&format!("    day_{module}::SOLUTION,\n"));
+    }
+    registry.push_str("];\n\n");
+
+    registry.push_str("pub const FAILED_TO_COMPILE: &[advent_of_code::template::PuzzleId] = &[\n");
+    for puzzle in &failed {
+        let (year, day) = puzzle.split_once('-').unwrap();
+        registry.push_str(&format!(
+            "    advent_of_code::template::PuzzleId::new(advent_of_code::year!({year}), advent_of_code::day!({})),\n",
+            day.parse::<u8>().unwrap()
+        ));
+    }
+    registry.push_str("];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, registry).unwrap();
+}
@@ -219,7 +219,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         // Answer to full input is 2578
         assert_eq!(result, Some(18));
     }
@@ -268,7 +268,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         // Answer to full puzzle is 1972
         assert_eq!(result, Some(9));
     }
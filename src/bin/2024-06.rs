@@ -24,7 +24,57 @@ use std::fmt::{Display, Formatter};
 use std::io;
 use std::io::Write;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, generate = generate);
+
+/// Generates a square lab of `130 * √size` tiles with a guard that leaves it, for `cargo stress`.
+fn generate(seed: u64, size: usize) -> String {
+    let side = (130.0 * (size as f64).sqrt()) as usize;
+    let mut rng = advent_of_code::template::stress::SplitMix64::new(seed);
+    loop {
+        let mut grid: Vec<Vec<u8>> = (0..side)
+            .map(|_| {
+                (0..side)
+                    .map(|_| if rng.below(20) == 0 { b'#' } else { b'.' })
+                    .collect()
+            })
+            .collect();
+        let x = rng.below(side as u64) as usize;
+        let y = rng.below(side as u64) as usize;
+        grid[y][x] = b'^';
+        // Part two expects the guard to leave the original map, so retry until it does.
+        if guard_exits(&grid, x, y) {
+            return grid
+                .into_iter()
+                .map(|row| String::from_utf8(row).unwrap())
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+    }
+}
+
+/// Walk the guard up from (x, y), turning right at obstacles, and report whether it leaves the grid.
+fn guard_exits(grid: &[Vec<u8>], mut x: usize, mut y: usize) -> bool {
+    let (mut dx, mut dy) = (0isize, -1isize);
+    let mut seen = HashSet::new();
+    loop {
+        if !seen.insert((x, y, dx, dy)) {
+            return false;
+        }
+        let (next_x, next_y) = (x as isize + dx, y as isize + dy);
+        if next_x < 0
+            || next_y < 0
+            || next_x as usize >= grid[0].len()
+            || next_y as usize >= grid.len()
+        {
+            return true;
+        }
+        if grid[next_y as usize][next_x as usize] == b'#' {
+            (dx, dy) = (-dy, dx);
+        } else {
+            (x, y) = (next_x as usize, next_y as usize);
+        }
+    }
+}
 
 /// Used to record a movement from one tile to another.
 #[derive(PartialEq, Hash, Clone, Debug, Eq)]
@@ -605,13 +655,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(38));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6));
     }
 }
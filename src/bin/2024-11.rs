@@ -1,7 +1,16 @@
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, generate = generate);
+
+/// Generates a line of `8 * size` stones for `cargo stress`.
+fn generate(seed: u64, size: usize) -> String {
+    let mut rng = advent_of_code::template::stress::SplitMix64::new(seed);
+    (0..8 * size)
+        .map(|_| rng.below(10_000_000).to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
 
 fn load_input(input: &str) -> Vec<u64> {
     input
@@ -179,13 +188,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(55312));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(65601038650482));
     }
 }
@@ -7,7 +7,7 @@
 use regex::Regex;
 use std::collections::HashMap;
 
-advent_of_code::solution!(14);
+advent_of_code::solution!(14, timeout = 10);
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 struct Position {
@@ -270,13 +270,13 @@ mod tests {
     use super::*;
     #[test]
     fn test_part_one() {
-        let result = do_part_one(&advent_of_code::template::read_file("examples", DAY), 11, 7);
+        let result = do_part_one(&advent_of_code::template::read_file("examples", PUZZLE), 11, 7);
         assert_eq!(result, Some(12));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
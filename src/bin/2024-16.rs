@@ -45,7 +45,7 @@ impl <'a> Maze<'a> {
     }
     
     fn load_input(input: &str) -> Vec<Vec<Tile>> {
-        let map :  Vec<Vec< crate::Tile>> = Vec::new();
+        let map :  Vec<Vec<Tile>> = Vec::new();
         let chars: Vec<Vec<char>> = input
             .lines()
             .map(|line| line.trim_end().chars().collect())
@@ -82,13 +82,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
@@ -0,0 +1,208 @@
+/// Runs the `all`, `time` and `stress` commands in-process, against every solution in `src/bin`.
+///
+/// Solutions are compiled into this binary as modules by `build.rs`. The `advent_of_code` binary builds it and hands
+/// these commands to it, so a day that doesn't compile only affects them, not `scaffold`, `download` or `solve`.
+use advent_of_code::template::commands::{all, stress, time};
+use advent_of_code::template::{solved_years, Year};
+use args::{parse, AppArguments};
+
+/// Table of all solutions in `src/bin`, generated by `build.rs`.
+/// Solution modules are left out of test and heap profiling builds, as their tests and allocators
+/// belong to the individual solution binaries.
+mod solutions {
+    #[cfg(not(any(test, feature = "dhat-heap")))]
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+
+    #[cfg(any(test, feature = "dhat-heap"))]
+    pub const SOLUTIONS: &[advent_of_code::template::Solution] = &[];
+
+    #[cfg(any(test, feature = "dhat-heap"))]
+    pub const FAILED_TO_COMPILE: &[advent_of_code::template::PuzzleId] = &[];
+}
+
+mod args {
+    use advent_of_code::template::stress;
+    use advent_of_code::template::{parse_puzzle, runner::BenchConfig, Day, PuzzleId, Year};
+    use std::{process, time::Duration};
+
+    pub enum AppArguments {
+        Stress {
+            puzzle: PuzzleId,
+            sizes: Vec<usize>,
+            seed: u64,
+        },
+        All {
+            year: Option<Year>,
+            verify: bool,
+            timeout: Option<Duration>,
+            jobs: usize,
+        },
+        Time {
+            year: Option<Year>,
+            all: bool,
+            day: Option<Day>,
+            store: bool,
+            bench_config: BenchConfig,
+            compare_threshold: Option<f64>,
+            timeout: Option<Duration>,
+            jobs: usize,
+        },
+    }
+
+    /// Parse the number of days to run concurrently. Defaults to one, i.e. running days sequentially.
+    fn parse_jobs(args: &mut pico_args::Arguments) -> Result<usize, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str::<_, usize>("--jobs")? {
+            Some(0) => Err("`--jobs` must be at least 1.".into()),
+            jobs => Ok(jobs.unwrap_or(1)),
+        }
+    }
+
+    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+        let mut args = pico_args::Arguments::from_env();
+
+        let app_args = match args.subcommand()?.as_deref() {
+            Some("all") => AppArguments::All {
+                year: args.opt_value_from_str("--year")?,
+                verify: args.contains("--verify"),
+                timeout: args
+                    .opt_value_from_str("--timeout")?
+                    .map(Duration::from_secs),
+                jobs: parse_jobs(&mut args)?,
+            },
+            Some("time") => {
+                let year = args.opt_value_from_str("--year")?;
+                let all = args.contains("--all");
+                let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let threshold: f64 = args.opt_value_from_str("--threshold")?.unwrap_or(5.0);
+                let timeout = args
+                    .opt_value_from_str("--timeout")?
+                    .map(Duration::from_secs);
+                let jobs = parse_jobs(&mut args)?;
+
+                let default = BenchConfig::default();
+                let bench_config = BenchConfig {
+                    budget: args
+                        .opt_value_from_str("--budget")?
+                        .map_or(default.budget, Duration::from_millis),
+                    warmup: args
+                        .opt_value_from_str("--warmup")?
+                        .map_or(default.warmup, Duration::from_millis),
+                };
+
+                AppArguments::Time {
+                    year,
+                    all,
+                    day: args.opt_free_from_str()?,
+                    store,
+                    bench_config,
+                    compare_threshold: compare.then_some(threshold),
+                    timeout,
+                    jobs,
+                }
+            }
+            Some("stress") => AppArguments::Stress {
+                sizes: args
+                    .opt_value_from_fn("--sizes", stress::parse_sizes)?
+                    .unwrap_or_else(|| vec![1, 2, 4, 8]),
+                seed: args.opt_value_from_str("--seed")?.unwrap_or(1),
+                puzzle: parse_puzzle(&mut args)?,
+            },
+            Some(x) => {
+                eprintln!("Unknown command: {x}");
+                process::exit(1);
+            }
+            None => {
+                eprintln!("No command specified.");
+                process::exit(1);
+            }
+        };
+
+        let remaining = args.finish();
+        if !remaining.is_empty() {
+            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+        }
+
+        Ok(app_args)
+    }
+}
+
+fn main() {
+    match parse() {
+        Err(err) => {
+            eprintln!("Error: {err}");
+            std::process::exit(1);
+        }
+        Ok(args) => match args {
+            AppArguments::Stress {
+                puzzle,
+                sizes,
+                seed,
+            } => {
+                if solutions::FAILED_TO_COMPILE.contains(&puzzle) {
+                    eprintln!("Day {} ({}) failed to compile.", puzzle.day, puzzle.year);
+                    std::process::exit(1);
+                }
+                stress::handle(solutions::SOLUTIONS, puzzle, &sizes, seed);
+            }
+            AppArguments::All {
+                year,
+                verify,
+                timeout,
+                jobs,
+            } => {
+                all::handle(
+                    solutions::SOLUTIONS,
+                    &years_to_run(year),
+                    verify,
+                    timeout,
+                    jobs,
+                    solutions::FAILED_TO_COMPILE,
+                );
+            }
+            AppArguments::Time {
+                year,
+                day,
+                all,
+                store,
+                bench_config,
+                compare_threshold,
+                timeout,
+                jobs,
+            } => {
+                for puzzle in solutions::FAILED_TO_COMPILE {
+                    eprintln!(
+                        "Not timing day {} ({}), as it failed to compile.",
+                        puzzle.day, puzzle.year
+                    );
+                }
+                time::handle(
+                    solutions::SOLUTIONS,
+                    &years_to_run(year),
+                    day,
+                    all,
+                    store,
+                    bench_config,
+                    compare_threshold,
+                    timeout,
+                    jobs,
+                );
+            }
+        },
+    };
+}
+
+/// The years run by `all` and `time`: the year passed with `--year`, or every year that has a solution, including
+/// solutions that failed to compile.
+fn years_to_run(year: Option<Year>) -> Vec<Year> {
+    year.map_or_else(
+        || {
+            let mut years = solved_years(solutions::SOLUTIONS);
+            years.extend(solutions::FAILED_TO_COMPILE.iter().map(|p| p.year));
+            years.sort_unstable();
+            years.dedup();
+            years
+        },
+        |year| vec![year],
+    )
+}
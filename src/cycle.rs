@@ -0,0 +1,259 @@
+/// Cycle detection for simulations whose next state depends only on the current one, e.g. a guard patrolling a
+/// lab or robots that wrap around the edges of a map.
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Index;
+
+/// A cycle in a sequence of states: the state at index `start + period` is the same as the one at `start`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// The index of the first state that is part of the cycle.
+    pub start: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The index of the earliest state that is the same as the state at index `n`.
+    pub fn index_of(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.period
+        }
+    }
+}
+
+/// Find the cycle in `initial`, `step(initial)`, `step(step(initial))`, ... with Floyd's tortoise and hare.
+/// Only keeps two states in memory, but calls `step` about three times per state before the cycle repeats.
+/// Never returns if the states don't repeat.
+pub fn floyd<T: Clone + Eq>(initial: T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut period = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+
+    Cycle { start, period }
+}
+
+/// Find the cycle in `initial`, `step(initial)`, `step(step(initial))`, ... with Brent's algorithm.
+/// Only keeps two states in memory and usually calls `step` less often than [`floyd`].
+/// Never returns if the states don't repeat.
+pub fn brent<T: Clone + Eq>(initial: T, mut step: impl FnMut(&T) -> T) -> Cycle {
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    tortoise = initial.clone();
+    hare = initial;
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, period }
+}
+
+/// Find the cycle in the states produced by repeatedly calling `step` on `initial`.
+/// Never returns if the states don't repeat.
+pub fn find_cycle<T: Clone + Eq>(initial: T, step: impl FnMut(&T) -> T) -> Cycle {
+    brent(initial, step)
+}
+
+/// The states of a simulation in the order they were seen, for simulations that are driven by the caller.
+/// Uses more memory than [`brent`], but finds a cycle as soon as a state repeats.
+#[derive(Clone, Debug)]
+pub struct History<T> {
+    states: Vec<T>,
+    seen: HashMap<T, usize>,
+}
+
+impl<T: Clone + Eq + Hash> History<T> {
+    pub fn new() -> Self {
+        Self {
+            states: vec![],
+            seen: HashMap::new(),
+        }
+    }
+
+    /// Record the next state. Returns the cycle, without recording the state, if it was seen before.
+    pub fn push(&mut self, state: T) -> Option<Cycle> {
+        if let Some(start) = self.seen.get(&state) {
+            return Some(Cycle {
+                start: *start,
+                period: self.states.len() - start,
+            });
+        }
+
+        self.seen.insert(state.clone(), self.states.len());
+        self.states.push(state);
+        None
+    }
+
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.states.get(index)
+    }
+
+    pub fn contains(&self, state: &T) -> bool {
+        self.seen.contains_key(state)
+    }
+}
+
+impl<T: Clone + Eq + Hash> Default for History<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Index<usize> for History<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.states[index]
+    }
+}
+
+/// The state after calling `step` on `initial` `n` times. Stops stepping as soon as a state repeats and looks up
+/// the answer in the cycle, so `n` can be far larger than the number of distinct states.
+pub fn fast_forward<T: Clone + Eq + Hash>(
+    initial: T,
+    mut step: impl FnMut(&T) -> T,
+    n: usize,
+) -> T {
+    let mut history = History::new();
+    let mut state = initial;
+
+    for _ in 0..n {
+        if let Some(cycle) = history.push(state.clone()) {
+            return history[cycle.index_of(n)].clone();
+        }
+        state = step(&state);
+    }
+
+    state
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{brent, fast_forward, find_cycle, floyd, Cycle, History};
+    use crate::geometry::Point;
+
+    // a sequence with a tail before it starts repeating.
+    fn next(x: &u64) -> u64 {
+        (x * x + 1) % 55
+    }
+
+    fn naive(initial: u64, n: usize) -> u64 {
+        (0..n).fold(initial, |x, _| next(&x))
+    }
+
+    #[test]
+    fn finds_cycles() {
+        let mut history = History::new();
+        let mut x = 0;
+        let cycle = loop {
+            if let Some(cycle) = history.push(x) {
+                break cycle;
+            }
+            x = next(&x);
+        };
+
+        assert_eq!(cycle.start > 0, true);
+        assert_eq!(naive(0, cycle.start), naive(0, cycle.start + cycle.period));
+        assert_eq!(floyd(0, next), cycle);
+        assert_eq!(brent(0, next), cycle);
+        assert_eq!(find_cycle(0, next), cycle);
+        assert_eq!(history.len(), cycle.start + cycle.period);
+        assert_eq!(history.contains(&2), true);
+    }
+
+    #[test]
+    fn finds_cycles_without_a_tail() {
+        assert_eq!(
+            find_cycle(0, |x| (x + 1) % 7),
+            Cycle {
+                start: 0,
+                period: 7
+            }
+        );
+        assert_eq!(
+            floyd(3, |x| *x),
+            Cycle {
+                start: 0,
+                period: 1
+            }
+        );
+    }
+
+    #[test]
+    fn maps_indices_into_the_cycle() {
+        let cycle = Cycle {
+            start: 3,
+            period: 4,
+        };
+        assert_eq!(cycle.index_of(2), 2);
+        assert_eq!(cycle.index_of(3), 3);
+        assert_eq!(cycle.index_of(7), 3);
+        assert_eq!(cycle.index_of(1_000_000_001), 5);
+    }
+
+    #[test]
+    fn fast_forwards() {
+        for n in [0, 1, 5, 12, 100, 1001] {
+            assert_eq!(fast_forward(0, next, n), naive(0, n));
+        }
+
+        // a robot on an 11 x 7 map is back at its start every 77 seconds.
+        let velocity = Point::new(2, -3);
+        let robot = |p: &Point| (*p + velocity).wrap(11, 7);
+        let start = Point::new(2, 4);
+        assert_eq!(find_cycle(start, robot).period, 77);
+        assert_eq!(
+            fast_forward(start, robot, 1_000_000),
+            (start + velocity * 1_000_000).wrap(11, 7)
+        );
+    }
+}
@@ -0,0 +1,368 @@
+/// Compass directions and 2D points for puzzles that move things around a plane.
+/// Like in [`Grid`], `y` grows downwards, so [`Dir4::Up`] is a step of `(0, -1)`.
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+use crate::grid::{Coord, Grid};
+
+/// Error returned when a character or string is not a known direction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseDirError {
+    pub found: String,
+}
+
+impl Display for ParseDirError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unexpected direction {:?}.", self.found)
+    }
+}
+
+impl Error for ParseDirError {}
+
+/// One of the four orthogonal directions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// All directions, clockwise starting from up.
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// The step of a single move in this direction.
+    pub fn delta(self) -> Point {
+        match self {
+            Self::Up => Point::new(0, -1),
+            Self::Right => Point::new(1, 0),
+            Self::Down => Point::new(0, 1),
+            Self::Left => Point::new(-1, 0),
+        }
+    }
+
+    /// Whether the direction moves along the x axis.
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Self::Left | Self::Right)
+    }
+}
+
+/// Parses `^>v<` as well as the compass points `NESW`.
+impl TryFrom<char> for Dir4 {
+    type Error = ParseDirError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'N' => Ok(Self::Up),
+            '>' | 'E' => Ok(Self::Right),
+            'v' | 'S' => Ok(Self::Down),
+            '<' | 'W' => Ok(Self::Left),
+            _ => Err(ParseDirError {
+                found: c.to_string(),
+            }),
+        }
+    }
+}
+
+impl FromStr for Dir4 {
+    type Err = ParseDirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::try_from(c),
+            _ => Err(ParseDirError { found: s.into() }),
+        }
+    }
+}
+
+/// Renders the direction as one of `^>v<`.
+impl Display for Dir4 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Self::Up => '^',
+            Self::Right => '>',
+            Self::Down => 'v',
+            Self::Left => '<',
+        };
+        write!(f, "{c}")
+    }
+}
+
+/// One of the eight compass directions, including the diagonal ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    /// All directions, clockwise starting from north.
+    pub const ALL: [Self; 8] = [
+        Self::N,
+        Self::NE,
+        Self::E,
+        Self::SE,
+        Self::S,
+        Self::SW,
+        Self::W,
+        Self::NW,
+    ];
+
+    /// Turn clockwise by 45 degrees.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Turn counter-clockwise by 45 degrees.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    /// The step of a single move in this direction.
+    pub fn delta(self) -> Point {
+        match self {
+            Self::N => Point::new(0, -1),
+            Self::NE => Point::new(1, -1),
+            Self::E => Point::new(1, 0),
+            Self::SE => Point::new(1, 1),
+            Self::S => Point::new(0, 1),
+            Self::SW => Point::new(-1, 1),
+            Self::W => Point::new(-1, 0),
+            Self::NW => Point::new(-1, -1),
+        }
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::Up => Self::N,
+            Dir4::Right => Self::E,
+            Dir4::Down => Self::S,
+            Dir4::Left => Self::W,
+        }
+    }
+}
+
+/// Parses the compass points `N`, `NE`, ..., `NW`, as well as `^>v<`.
+impl FromStr for Dir8 {
+    type Err = ParseDirError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "NE" => Ok(Self::NE),
+            "SE" => Ok(Self::SE),
+            "SW" => Ok(Self::SW),
+            "NW" => Ok(Self::NW),
+            _ => s.parse::<Dir4>().map(Self::from),
+        }
+    }
+}
+
+impl Display for Dir8 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// A point or vector in the plane.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T = i64> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Point {
+    pub const ORIGIN: Self = Self::new(0, 0);
+
+    /// The point one step away in direction `dir`.
+    pub fn step(self, dir: impl Into<Dir8>) -> Self {
+        self + dir.into().delta()
+    }
+
+    pub fn manhattan(self, other: Self) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// Wrap the point onto a torus of `width` by `height`, e.g. for robots that teleport across the edges.
+    pub fn wrap(self, width: i64, height: i64) -> Self {
+        Self::new(self.x.rem_euclid(width), self.y.rem_euclid(height))
+    }
+
+    /// The grid coordinate of the point, or `None` if it is negative.
+    pub fn to_coord(self) -> Option<Coord> {
+        Some(Coord::new(
+            usize::try_from(self.x).ok()?,
+            usize::try_from(self.y).ok()?,
+        ))
+    }
+
+    /// The coordinate of the point in `grid`, or `None` if it lies outside of it.
+    pub fn to_coord_in<T>(self, grid: &Grid<T>) -> Option<Coord> {
+        self.to_coord().filter(|c| grid.contains(*c))
+    }
+}
+
+impl TryFrom<Coord> for Point {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(coord: Coord) -> Result<Self, Self::Error> {
+        Ok(Self::new(i64::try_from(coord.x)?, i64::try_from(coord.y)?))
+    }
+}
+
+impl<T: Display> Display for Point<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+/// Scales the vector, e.g. the velocity of a robot by the number of elapsed seconds.
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self {
+        Self::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Dir4, Dir8, Point};
+    use crate::grid::{Coord, Grid};
+
+    #[test]
+    fn parses_directions() {
+        let moves: Vec<Dir4> = "^>v<".chars().map(|c| c.try_into().unwrap()).collect();
+        assert_eq!(moves, Dir4::ALL);
+        assert_eq!("W".parse::<Dir4>(), Ok(Dir4::Left));
+        assert_eq!("x".parse::<Dir4>().is_err(), true);
+        assert_eq!("^^".parse::<Dir4>().is_err(), true);
+        assert_eq!("SW".parse::<Dir8>(), Ok(Dir8::SW));
+        assert_eq!("N".parse::<Dir8>(), Ok(Dir8::N));
+        assert_eq!(Dir4::Down.to_string(), "v");
+    }
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+        assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+        assert_eq!(Dir4::Left.reverse(), Dir4::Right);
+        assert_eq!(Dir8::N.turn_right(), Dir8::NE);
+        assert_eq!(Dir8::N.turn_left(), Dir8::NW);
+        assert_eq!(Dir8::NE.reverse(), Dir8::SW);
+        assert_eq!(Dir8::SE.is_diagonal(), true);
+
+        for dir in Dir8::ALL {
+            assert_eq!(dir.delta() + dir.reverse().delta(), Point::ORIGIN);
+        }
+    }
+
+    #[test]
+    fn does_vector_arithmetic() {
+        let p = Point::new(2, 3);
+        assert_eq!(p + Point::new(1, -1), Point::new(3, 2));
+        assert_eq!(p - Point::new(2, 3), Point::ORIGIN);
+        assert_eq!(-p, Point::new(-2, -3));
+        assert_eq!(p * 3, Point::new(6, 9));
+        assert_eq!(p.step(Dir4::Up), Point::new(2, 2));
+        assert_eq!(p.step(Dir8::SW), Point::new(1, 4));
+        assert_eq!(p.manhattan(Point::new(-1, 7)), 7);
+
+        let mut q = p;
+        q += Point::new(1, 1);
+        q -= Point::new(0, 2);
+        assert_eq!(q, Point::new(3, 2));
+    }
+
+    #[test]
+    fn wraps_on_a_torus() {
+        let robot = Point::new(2, 4) + Point::new(2, -3) * 5;
+        assert_eq!(robot.wrap(11, 7), Point::new(1, 3));
+    }
+
+    #[test]
+    fn converts_to_grid_coordinates() {
+        let grid = Grid::new(3, 2, '.');
+        assert_eq!(Point::new(2, 1).to_coord_in(&grid), Some(Coord::new(2, 1)));
+        assert_eq!(Point::new(3, 1).to_coord_in(&grid), None);
+        assert_eq!(Point::new(-1, 0).to_coord(), None);
+        assert_eq!(Point::try_from(Coord::new(4, 5)), Ok(Point::new(4, 5)));
+    }
+}
@@ -0,0 +1,527 @@
+/// A dense 2D grid for puzzle maps, with typed coordinates and bounds-checked neighbors.
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+use crate::geometry::Dir8;
+
+/// A position in a grid: column `x` and row `y`, with `(0, 0)` in the top left corner.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Coord {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Coord {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// The coordinate `dx` columns and `dy` rows away, or `None` if that is left of or above the origin.
+    pub fn offset(self, dx: isize, dy: isize) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(dx)?,
+            y: self.y.checked_add_signed(dy)?,
+        })
+    }
+}
+
+impl Display for Coord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/// Offsets of the four orthogonal neighbors: up, right, down, left.
+pub const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of all eight neighbors, clockwise starting from up.
+pub const OFFSETS_8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// Errors that can occur when parsing a grid. Lines and columns are 1-based.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// The input has no rows.
+    Empty,
+    /// A row has a different length than the first row.
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The cell parser rejected a character.
+    InvalidChar {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "the grid is empty."),
+            Self::RaggedRow {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line}: expected a row of {expected} cells, found {found}."
+            ),
+            Self::InvalidChar {
+                line,
+                column,
+                found,
+            } => write!(
+                f,
+                "line {line}, column {column}: unexpected character {found:?}."
+            ),
+        }
+    }
+}
+
+impl Error for ParseGridError {}
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A grid of `width` by `height` cells, all set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// A grid of `width` by `height` cells, set to the result of `f` for each coordinate.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Coord) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Coord::new(x, y)))
+            .map(&mut f)
+            .collect();
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parse a grid with one row per line, converting each character with `f`.
+    /// Trailing line breaks are ignored. Returns an error if `f` rejects a character or the rows differ in length.
+    pub fn parse_with(
+        input: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseGridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for (y, line) in input.trim_end_matches(['\r', '\n']).lines().enumerate() {
+            let mut row_width = 0;
+
+            for (x, c) in line.chars().enumerate() {
+                let cell = f(c).ok_or(ParseGridError::InvalidChar {
+                    line: y + 1,
+                    column: x + 1,
+                    found: c,
+                })?;
+                cells.push(cell);
+                row_width += 1;
+            }
+
+            match width {
+                None => width = Some(row_width),
+                Some(expected) if expected != row_width => {
+                    return Err(ParseGridError::RaggedRow {
+                        line: y + 1,
+                        expected,
+                        found: row_width,
+                    });
+                }
+                Some(_) => {}
+            }
+
+            height += 1;
+        }
+
+        match width {
+            Some(width) if width > 0 => Ok(Self {
+                width,
+                height,
+                cells,
+            }),
+            _ => Err(ParseGridError::Empty),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `coord` lies within the grid.
+    pub fn contains(&self, coord: Coord) -> bool {
+        coord.x < self.width && coord.y < self.height
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.index_of(coord).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        self.index_of(coord).map(|i| &mut self.cells[i])
+    }
+
+    /// Set the cell at `coord`. Returns the previous value, or `None` if `coord` is out of bounds.
+    pub fn set(&mut self, coord: Coord, value: T) -> Option<T> {
+        self.get_mut(coord)
+            .map(|cell| std::mem::replace(cell, value))
+    }
+
+    fn index_of(&self, coord: Coord) -> Option<usize> {
+        self.contains(coord).then(|| coord.y * self.width + coord.x)
+    }
+
+    /// The coordinate of the cell at `index`. There are no cells if `width` is 0, so it is never called then.
+    fn coord_of(&self, index: usize) -> Coord {
+        Coord::new(index % self.width, index / self.width)
+    }
+
+    /// The coordinate `dx` columns and `dy` rows away from `coord`, if it lies within the grid.
+    pub fn offset(&self, coord: Coord, dx: isize, dy: isize) -> Option<Coord> {
+        coord.offset(dx, dy).filter(|c| self.contains(*c))
+    }
+
+    /// All coordinates of the grid, row by row.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Coord::new(x, y)))
+    }
+
+    /// All cells of the grid with their coordinates, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.coord_of(i), cell))
+    }
+
+    /// The neighbor of `coord` in direction `dir`, if it lies within the grid.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn step(&self, coord: Coord, dir: impl Into<Dir8>) -> Option<Coord> {
+        let delta = dir.into().delta();
+        self.offset(coord, delta.x as isize, delta.y as isize)
+    }
+
+    /// The orthogonal neighbors of `coord` that lie within the grid.
+    pub fn neighbors4(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        OFFSETS_4
+            .iter()
+            .filter_map(move |(dx, dy)| self.offset(coord, *dx, *dy))
+    }
+
+    /// All neighbors of `coord`, including diagonal ones, that lie within the grid.
+    pub fn neighbors8(&self, coord: Coord) -> impl Iterator<Item = Coord> + '_ {
+        OFFSETS_8
+            .iter()
+            .filter_map(move |(dx, dy)| self.offset(coord, *dx, *dy))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // not `chunks(width)`, which panics for a grid without columns.
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// The cells of column `x`, top to bottom. Panics if `x` is out of bounds, like [`Grid::row`].
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} is out of bounds for a grid of width {}",
+            self.width
+        );
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// The cells from `start` in steps of `(dx, dy)` until the edge of the grid, starting with `start` itself.
+    pub fn walk(&self, start: Coord, dx: isize, dy: isize) -> impl Iterator<Item = (Coord, &T)> {
+        std::iter::successors(Some(start).filter(|c| self.contains(*c)), move |c| {
+            self.offset(*c, dx, dy)
+        })
+        .map(|c| (c, &self[c]))
+    }
+
+    /// The cells from `start` down and to the right.
+    pub fn diagonal(&self, start: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        self.walk(start, 1, 1)
+    }
+
+    /// The cells from `start` down and to the left.
+    pub fn anti_diagonal(&self, start: Coord) -> impl Iterator<Item = (Coord, &T)> {
+        self.walk(start, -1, 1)
+    }
+
+    /// The coordinate of the first cell, row by row, that matches `predicate`.
+    pub fn find(&self, predicate: impl FnMut(&T) -> bool) -> Option<Coord> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| self.coord_of(i))
+    }
+
+    /// The coordinate of the first cell that equals `value`.
+    pub fn position(&self, value: &T) -> Option<Coord>
+    where
+        T: PartialEq,
+    {
+        self.find(|cell| cell == value)
+    }
+
+    /// The coordinates of all cells that equal `value`, row by row.
+    pub fn positions<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Coord> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(coord, _)| coord)
+    }
+
+    /// A grid of the same size with `f` applied to each cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Parse a grid of single digits, e.g. a height map.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn parse_digits(input: &str) -> Result<Self, ParseGridError> {
+        Self::parse_with(input, |c| c.to_digit(10).map(|d| d as u8))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, Some)
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &T {
+        self.get(coord).unwrap_or_else(|| {
+            panic!(
+                "{coord} is out of bounds of a {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{coord} is out of bounds of a {width}x{height} grid"))
+    }
+}
+
+/// Renders the grid row by row, e.g. the map of a `Grid<char>` as it was parsed.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Coord, Grid, ParseGridError};
+    use crate::geometry::{Dir4, Dir8};
+
+    const MAP: &str = "#..\n.#.\n..@\n";
+
+    fn map() -> Grid<char> {
+        MAP.parse().unwrap()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = map();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid[Coord::new(2, 2)], '@');
+        assert_eq!(grid.get(Coord::new(3, 0)), None);
+        assert_eq!(grid.to_string(), MAP.trim_end());
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        assert_eq!("".parse::<Grid<char>>(), Err(ParseGridError::Empty));
+        assert_eq!(
+            "##\n#\n".parse::<Grid<char>>(),
+            Err(ParseGridError::RaggedRow {
+                line: 2,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            Grid::parse_digits("12\n3x"),
+            Err(ParseGridError::InvalidChar {
+                line: 2,
+                column: 2,
+                found: 'x'
+            })
+        );
+        assert_eq!(
+            Grid::parse_digits("12\n3x").unwrap_err().to_string(),
+            "line 2, column 2: unexpected character 'x'."
+        );
+    }
+
+    #[test]
+    fn handles_crlf_input() {
+        let grid: Grid<char> = "ab\r\ncd\r\n".parse().unwrap();
+        assert_eq!(grid.row(1), &['c', 'd']);
+    }
+
+    #[test]
+    fn finds_neighbors() {
+        let grid = map();
+        let corner: Vec<Coord> = grid.neighbors4(Coord::new(0, 0)).collect();
+        assert_eq!(corner, vec![Coord::new(1, 0), Coord::new(0, 1)]);
+        assert_eq!(grid.neighbors8(Coord::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbors8(Coord::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbors4(Coord::new(2, 1)).count(), 3);
+    }
+
+    #[test]
+    fn views_rows_columns_and_diagonals() {
+        let grid = map();
+        assert_eq!(grid.row(0), &['#', '.', '.']);
+        assert_eq!(grid.column(1).collect::<String>(), ".#.");
+        assert_eq!(
+            grid.diagonal(Coord::new(0, 0))
+                .map(|(_, c)| *c)
+                .collect::<String>(),
+            "##@"
+        );
+        assert_eq!(
+            grid.anti_diagonal(Coord::new(2, 0))
+                .map(|(_, c)| *c)
+                .collect::<String>(),
+            ".#."
+        );
+        assert_eq!(grid.walk(Coord::new(5, 5), 1, 0).count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_columns_out_of_bounds() {
+        let grid: Grid<char> = "abc\ndef".parse().unwrap();
+        grid.column(3).count();
+    }
+
+    #[test]
+    fn handles_grids_without_columns() {
+        let grid = Grid::new(0, 3, '.');
+        assert_eq!(grid.to_string(), "\n\n");
+        assert_eq!(grid.rows().count(), 3);
+        assert_eq!(grid.iter().count(), 0);
+        assert_eq!(grid.position(&'.'), None);
+        assert_eq!(Grid::from_fn(0, 2, |_| 0).coords().count(), 0);
+    }
+
+    #[test]
+    fn finds_positions() {
+        let grid = map();
+        assert_eq!(grid.position(&'@'), Some(Coord::new(2, 2)));
+        assert_eq!(grid.find(|c| *c == '?'), None);
+        assert_eq!(
+            grid.positions(&'#').collect::<Vec<_>>(),
+            vec![Coord::new(0, 0), Coord::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn updates_cells() {
+        let mut grid = Grid::new(2, 2, 0u8);
+        assert_eq!(grid.set(Coord::new(1, 0), 5), Some(0));
+        assert_eq!(grid.set(Coord::new(2, 0), 5), None);
+        grid[Coord::new(0, 1)] += 1;
+        assert_eq!(grid.to_string(), "05\n10");
+
+        let doubled = grid.map(|x| x * 2);
+        assert_eq!(doubled.iter().map(|(_, x)| *x).sum::<u8>(), 12);
+
+        let sums = Grid::from_fn(3, 2, |c| c.x + c.y);
+        assert_eq!(sums.to_string(), "012\n123");
+    }
+
+    #[test]
+    fn steps_in_directions() {
+        let grid = map();
+        assert_eq!(
+            grid.step(Coord::new(1, 1), Dir4::Up),
+            Some(Coord::new(1, 0))
+        );
+        assert_eq!(
+            grid.step(Coord::new(1, 1), Dir8::SE),
+            Some(Coord::new(2, 2))
+        );
+        assert_eq!(grid.step(Coord::new(0, 0), Dir4::Left), None);
+    }
+
+    #[test]
+    fn offsets_coordinates() {
+        assert_eq!(Coord::new(0, 0).offset(-1, 0), None);
+        assert_eq!(Coord::new(1, 1).offset(-1, 1), Some(Coord::new(0, 2)));
+        assert_eq!(map().offset(Coord::new(2, 2), 1, 0), None);
+    }
+}
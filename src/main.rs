@@ -1,81 +1,123 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{download, examples, read, registry, scaffold, solve};
+use advent_of_code::template::ExampleAnswers;
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::commands::scaffold::Overwrite;
+#[cfg(feature = "today")]
+use advent_of_code::template::{Day, PuzzleId, Year};
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::commands::scaffold::Overwrite;
+    #[cfg(feature = "today")]
+    use advent_of_code::template::Year;
+    use advent_of_code::template::{parse_puzzle, InputSource, PuzzleId};
+    use std::{env, process};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
+            force: bool,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Examples {
+            puzzle: PuzzleId,
+            blocks: Vec<usize>,
+            list: bool,
+            overwrite: bool,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
-            overwrite: bool,
+            overwrite: Overwrite,
+            force: bool,
+            part1_example: Option<String>,
+            part2_example: Option<String>,
+            update_tests: bool,
+            template: Option<String>,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            record: bool,
+            input: InputSource,
         },
-        All {
-            release: bool,
-        },
-        Time {
-            all: bool,
-            day: Option<Day>,
-            store: bool,
+        /// `all`, `time` or `stress`, with all arguments, which run in the `registry` binary.
+        Registry {
+            args: Vec<String>,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            year: Option<Year>,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        let mut raw_args: Vec<String> = env::args().skip(1).collect();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
-            Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
+        if raw_args
+            .first()
+            .is_some_and(|x| ["all", "time", "stress"].contains(&x.as_str()))
+        {
+            return Ok(AppArguments::Registry { args: raw_args });
+        }
 
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
-                }
-            }
+        // `--example` takes an optional value, which pico-args does not support.
+        let input = if raw_args.first().is_some_and(|x| x == "solve") {
+            InputSource::take_from_args(&mut raw_args)?
+        } else {
+            InputSource::Puzzle
+        };
+
+        let mut args =
+            pico_args::Arguments::from_vec(raw_args.into_iter().map(Into::into).collect());
+
+        let app_args = match args.subcommand()?.as_deref() {
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
+                force: args.contains("--force"),
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
+            },
+            Some("examples") => AppArguments::Examples {
+                blocks: args.values_from_str("--block")?,
+                list: args.contains("--list"),
+                overwrite: args.contains("--overwrite"),
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
                 download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
+                overwrite: Overwrite {
+                    module: args.contains("--overwrite"),
+                    input: args.contains("--overwrite-input"),
+                    example: args.contains("--overwrite-example"),
+                },
+                force: args.contains("--force"),
+                part1_example: args.opt_value_from_str("--part1-example")?,
+                part2_example: args.opt_value_from_str("--part2-example")?,
+                update_tests: args.contains("--update-tests"),
+                template: args.opt_value_from_str("--template")?,
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                record: args.contains("--record"),
+                input,
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                year: args.opt_value_from_str("--year")?,
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -102,33 +144,63 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::Registry { args } => registry::handle(&args),
+            AppArguments::Download { puzzle, force } => download::handle(puzzle, force),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Examples {
+                puzzle,
+                blocks,
+                list,
+                overwrite,
+            } => examples::handle(puzzle, &blocks, list, overwrite),
             AppArguments::Scaffold {
-                day,
+                puzzle,
                 download,
                 overwrite,
+                force,
+                part1_example,
+                part2_example,
+                update_tests,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                // answers passed as flags take precedence over the sidecar file of the examples.
+                let answers = ExampleAnswers {
+                    part_one: part1_example,
+                    part_two: part2_example,
+                }
+                .or(ExampleAnswers::read_from_file(puzzle));
+
+                if update_tests {
+                    scaffold::update_tests(puzzle, &answers);
+                    return;
+                }
+
+                scaffold::handle(puzzle, overwrite, &answers, template);
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle, force);
                 }
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                record,
+                input,
+            } => solve::handle(puzzle, release, dhat, submit, record, &input),
             #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+            AppArguments::Today { year } => {
+                match year.or_else(Year::today).zip(Day::today()) {
+                    Some((year, day)) => {
+                        let puzzle = PuzzleId::new(year, day);
+                        scaffold::handle(
+                            puzzle,
+                            Overwrite::default(),
+                            &ExampleAnswers::default(),
+                            None,
+                        );
+                        download::handle(puzzle, false);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
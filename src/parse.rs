@@ -0,0 +1,440 @@
+/// Helpers for parsing puzzle inputs: integer extraction, blank-line sections, `key: value` and `a|b` pairs, and a
+/// small [`Cursor`] for inputs with more structure. Errors report the 1-based line and column of the problem.
+use std::error::Error;
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// Error returned when an input does not have the expected shape.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}.",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// All integers in `s`, e.g. `[0, 4, 3, -3]` for `p=0,4 v=3,-3`. A `-` is only read as a sign if it is not preceded
+/// by a letter or digit, so `1-3` yields `[1, 3]`. Integers that don't fit an `i64` are skipped.
+pub fn ints(s: &str) -> Vec<i64> {
+    let bytes = s.as_bytes();
+    let mut numbers = vec![];
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let negative = bytes[i] == b'-'
+            && bytes.get(i + 1).is_some_and(u8::is_ascii_digit)
+            && (i == 0 || !bytes[i - 1].is_ascii_alphanumeric());
+
+        if !negative && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+
+        let start = i;
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+
+        if let Ok(n) = s[start..i].parse() {
+            numbers.push(n);
+        }
+    }
+
+    numbers
+}
+
+/// Split `input` into the sections between blank lines, e.g. the rules and the updates of a safety manual.
+/// Leading, trailing and repeated blank lines don't produce empty sections.
+pub fn sections(input: &str) -> Vec<&str> {
+    let mut sections = vec![];
+    let mut start = None;
+    let mut offset = 0;
+
+    for line in input.split_inclusive('\n') {
+        let blank = line.trim().is_empty();
+
+        match (start, blank) {
+            (None, false) => start = Some(offset),
+            (Some(s), true) => {
+                sections.push(input[s..offset].trim_end());
+                start = None;
+            }
+            _ => {}
+        }
+
+        offset += line.len();
+    }
+
+    if let Some(s) = start {
+        sections.push(input[s..].trim_end());
+    }
+
+    sections
+}
+
+/// Parse the trimmed `s`, which starts at the 1-based `column` of its line.
+fn value_at<T: FromStr>(s: &str, column: usize) -> Result<T, ParseError> {
+    let column = column + s.chars().take_while(|c| c.is_whitespace()).count();
+    let value = s.trim();
+
+    if value.is_empty() {
+        return Err(ParseError::new(1, column, "expected a value"));
+    }
+
+    value
+        .parse()
+        .map_err(|_| ParseError::new(1, column, format!("invalid value {value:?}")))
+}
+
+/// Parse a line like `47|53` into its two halves, which are trimmed before parsing.
+pub fn pair<A: FromStr, B: FromStr>(s: &str, separator: &str) -> Result<(A, B), ParseError> {
+    let Some((a, b)) = s.split_once(separator) else {
+        return Err(ParseError::new(
+            1,
+            s.chars().count() + 1,
+            format!("expected `{separator}`"),
+        ));
+    };
+
+    let b_column = a.chars().count() + separator.chars().count() + 1;
+    Ok((value_at(a, 1)?, value_at(b, b_column)?))
+}
+
+/// Parse a line like `Button A: X+94` into its key and value.
+pub fn key_value<K: FromStr, V: FromStr>(s: &str) -> Result<(K, V), ParseError> {
+    pair(s, ":")
+}
+
+/// Parse a line like `190: 10 19` into its key and the whitespace separated values after the colon.
+pub fn key_values<K: FromStr, V: FromStr>(s: &str) -> Result<(K, Vec<V>), ParseError> {
+    // checks the key and that there is at least one value.
+    let (key, _): (K, String) = key_value(s)?;
+    let (prefix, values) = s.split_once(':').unwrap();
+    let mut column = prefix.chars().count() + 2;
+    let mut parsed = vec![];
+
+    for value in values.split_inclusive(char::is_whitespace) {
+        if !value.trim().is_empty() {
+            parsed.push(value_at(value, column)?);
+        }
+        column += value.chars().count();
+    }
+
+    Ok((key, parsed))
+}
+
+/// Parse every line of `input` with `f`, reporting errors at their line in `input`.
+pub fn lines_with<T>(
+    input: &str,
+    mut f: impl FnMut(&str) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            f(line).map_err(|e| ParseError {
+                line: e.line + i,
+                ..e
+            })
+        })
+        .collect()
+}
+
+/// A position in an input with small parsers that consume it from the front and report errors where they fail.
+#[derive(Clone, Debug)]
+pub struct Cursor<'a> {
+    input: &'a str,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { input, offset: 0 }
+    }
+
+    /// The part of the input that has not been consumed yet.
+    pub fn rest(&self) -> &'a str {
+        &self.input[self.offset..]
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    /// The 1-based line and column of the cursor.
+    pub fn position(&self) -> (usize, usize) {
+        let consumed = &self.input[..self.offset];
+        let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+        (
+            consumed.matches('\n').count() + 1,
+            consumed[line_start..].chars().count() + 1,
+        )
+    }
+
+    /// An error at the position of the cursor.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        let (line, column) = self.position();
+        ParseError::new(line, column, message)
+    }
+
+    /// An error for something `expected` that isn't at the cursor, describing what is there instead.
+    fn unexpected(&self, expected: &str) -> ParseError {
+        match self.peek() {
+            Some(c) => self.error(format!("expected {expected}, found {c:?}")),
+            None => self.error(format!("expected {expected}, found the end of the input")),
+        }
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    /// Consume the next character.
+    pub fn advance(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.offset += c.len_utf8();
+        Some(c)
+    }
+
+    /// Consume `literal` if the input continues with it.
+    pub fn eat(&mut self, literal: &str) -> bool {
+        let found = self.rest().starts_with(literal);
+        if found {
+            self.offset += literal.len();
+        }
+        found
+    }
+
+    /// Consume `literal`, or fail if the input doesn't continue with it.
+    pub fn expect(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.eat(literal) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{literal}`")))
+        }
+    }
+
+    /// Consume the longest prefix whose characters match `predicate`, which may be empty.
+    pub fn take_while(&mut self, predicate: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.rest();
+        let length = rest.find(|c| !predicate(c)).unwrap_or(rest.len());
+        self.offset += length;
+        &rest[..length]
+    }
+
+    /// Skip spaces and tabs, but not line breaks.
+    pub fn skip_spaces(&mut self) {
+        self.take_while(|c| c == ' ' || c == '\t');
+    }
+
+    /// Consume an integer with an optional sign.
+    pub fn int<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let start = self.clone();
+        let sign = usize::from(self.eat("-") || self.eat("+"));
+        let digits = self.take_while(|c| c.is_ascii_digit());
+
+        if digits.is_empty() {
+            *self = start;
+            return Err(self.unexpected("a number"));
+        }
+
+        start.rest()[..sign + digits.len()].parse().map_err(|_| {
+            let error = start.error("number out of range");
+            *self = start;
+            error
+        })
+    }
+
+    /// Consume a non-empty run of letters and digits.
+    pub fn word(&mut self) -> Result<&'a str, ParseError> {
+        match self.take_while(char::is_alphanumeric) {
+            "" => Err(self.unexpected("a word")),
+            word => Ok(word),
+        }
+    }
+
+    /// Consume a line break, or succeed at the end of the input.
+    pub fn end_of_line(&mut self) -> Result<(), ParseError> {
+        if self.is_done() || self.eat("\n") || self.eat("\r\n") {
+            Ok(())
+        } else {
+            Err(self.unexpected("the end of the line"))
+        }
+    }
+
+    /// Fail unless the whole input, except trailing whitespace, has been consumed.
+    pub fn finish(&mut self) -> Result<(), ParseError> {
+        self.take_while(char::is_whitespace);
+        if self.is_done() {
+            Ok(())
+        } else {
+            Err(self.unexpected("the end of the input"))
+        }
+    }
+
+    /// Run `parser`, and put the cursor back where it was if it fails.
+    pub fn attempt<T>(
+        &mut self,
+        parser: impl FnOnce(&mut Self) -> Result<T, ParseError>,
+    ) -> Option<T> {
+        let start = self.offset;
+        let result = parser(self).ok();
+        if result.is_none() {
+            self.offset = start;
+        }
+        result
+    }
+
+    /// Run `parser` one or more times, with `separator` between the runs, e.g. for `75,47,61`.
+    pub fn separated<T>(
+        &mut self,
+        separator: &str,
+        mut parser: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut values = vec![parser(self)?];
+        while self.eat(separator) {
+            values.push(parser(self)?);
+        }
+        Ok(values)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ints, key_value, key_values, lines_with, pair, sections, Cursor, ParseError};
+
+    #[test]
+    fn extracts_integers() {
+        assert_eq!(ints("p=0,4 v=3,-3"), vec![0, 4, 3, -3]);
+        assert_eq!(ints("Button A: X+94, Y-34"), vec![94, 34]);
+        assert_eq!(ints("1-3 a: -12"), vec![1, 3, -12]);
+        assert_eq!(ints("3   4"), vec![3, 4]);
+        assert_eq!(ints("no numbers - here"), Vec::<i64>::new());
+    }
+
+    #[test]
+    fn splits_sections() {
+        let input = "\n47|53\n97|13\n\n\n75,47\r\n\r\n61,13\n";
+        assert_eq!(sections(input), vec!["47|53\n97|13", "75,47", "61,13"]);
+        assert_eq!(sections(""), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn parses_pairs() {
+        assert_eq!(pair::<u32, u32>("47|53", "|"), Ok((47, 53)));
+        assert_eq!(key_value::<String, i32>("x00: 1"), Ok(("x00".into(), 1)));
+        assert_eq!(
+            key_values::<u64, u64>("190: 10 19"),
+            Ok((190, vec![10, 19]))
+        );
+
+        assert_eq!(
+            pair::<u32, u32>("47-53", "|"),
+            Err(ParseError::new(1, 6, "expected `|`"))
+        );
+        assert_eq!(
+            pair::<u32, u32>("47| x", "|").unwrap_err().to_string(),
+            "line 1, column 5: invalid value \"x\"."
+        );
+        assert_eq!(
+            key_values::<u64, u64>("190: 10 1x 19").unwrap_err().column,
+            9
+        );
+    }
+
+    #[test]
+    fn reports_lines() {
+        let parsed = lines_with("1|2\n3|4", |line| pair::<u8, u8>(line, "|"));
+        assert_eq!(parsed, Ok(vec![(1, 2), (3, 4)]));
+
+        let error = lines_with("1|2\n3|4\n5|", |line| pair::<u8, u8>(line, "|")).unwrap_err();
+        assert_eq!((error.line, error.column), (3, 3));
+    }
+
+    #[test]
+    fn parses_with_a_cursor() {
+        let mut cursor = Cursor::new("Register A: 729\nProgram: 0,1,5,4\n");
+        cursor.expect("Register ").unwrap();
+        assert_eq!(cursor.word(), Ok("A"));
+        cursor.expect(":").unwrap();
+        cursor.skip_spaces();
+        assert_eq!(cursor.int::<u64>(), Ok(729));
+        cursor.end_of_line().unwrap();
+        cursor.expect("Program: ").unwrap();
+        assert_eq!(
+            cursor.separated(",", Cursor::int::<u8>),
+            Ok(vec![0, 1, 5, 4])
+        );
+        cursor.end_of_line().unwrap();
+        assert_eq!(cursor.finish(), Ok(()));
+    }
+
+    #[test]
+    fn reports_positions() {
+        let mut cursor = Cursor::new("a: 1\nb: -x");
+        cursor.take_while(|c| c != '-');
+        assert_eq!(cursor.position(), (2, 4));
+
+        let error = cursor.int::<i32>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 4: expected a number, found '-'."
+        );
+        assert_eq!(cursor.expect("+").unwrap_err().column, 4);
+        assert_eq!(
+            Cursor::new("300").int::<u8>().unwrap_err().message,
+            "number out of range"
+        );
+    }
+
+    #[test]
+    fn backtracks_failed_attempts() {
+        // scan corrupted memory for `mul(a,b)` instructions.
+        let mut cursor = Cursor::new("xmul(2,4)%mul(32,64]mul(11,8)");
+        let mut products = vec![];
+
+        while !cursor.is_done() {
+            let mul = cursor.attempt(|c| {
+                c.expect("mul(")?;
+                let a: u32 = c.int()?;
+                c.expect(",")?;
+                let b: u32 = c.int()?;
+                c.expect(")")?;
+                Ok(a * b)
+            });
+
+            match mul {
+                Some(product) => products.push(product),
+                None => {
+                    cursor.advance();
+                }
+            }
+        }
+
+        assert_eq!(products, vec![8, 88]);
+    }
+}
@@ -0,0 +1,514 @@
+/// Searches over implicit graphs, where the edges of a node are produced by a `successors` closure.
+///
+/// Nodes can be anything hashable, e.g. a [`Coord`](crate::grid::Coord) or a `(Coord, Dir4)` state for mazes where
+/// turning costs extra.
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The nodes reached by a search from `start`, with their distance and their predecessors on all shortest paths.
+#[derive(Clone, Debug)]
+pub struct Paths<N, C = usize> {
+    pub start: N,
+    pub distances: HashMap<N, C>,
+    /// The nodes that precede a node on at least one shortest path to it.
+    pub predecessors: HashMap<N, Vec<N>>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Paths<N, C> {
+    fn new(start: N, zero: C) -> Self {
+        Self {
+            distances: HashMap::from([(start.clone(), zero)]),
+            predecessors: HashMap::new(),
+            start,
+        }
+    }
+
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    /// A shortest path from the start to `goal`, including both, or `None` if `goal` was not reached.
+    pub fn path_to(&self, goal: &N) -> Option<Vec<N>> {
+        if !self.distances.contains_key(goal) {
+            return None;
+        }
+
+        let mut path = vec![goal.clone()];
+        while let Some(previous) = self
+            .predecessors
+            .get(path.last().unwrap())
+            .and_then(|p| p.first())
+        {
+            path.push(previous.clone());
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// All nodes that lie on at least one shortest path from the start to `goal`, including both.
+    pub fn nodes_on_shortest_paths(&self, goal: &N) -> HashSet<N> {
+        let mut nodes = HashSet::new();
+
+        if !self.distances.contains_key(goal) {
+            return nodes;
+        }
+
+        let mut stack = vec![goal.clone()];
+        while let Some(node) = stack.pop() {
+            if let Some(predecessors) = self.predecessors.get(&node) {
+                stack.extend(predecessors.iter().filter(|p| !nodes.contains(*p)).cloned());
+            }
+            nodes.insert(node);
+        }
+
+        nodes
+    }
+
+    /// The number of distinct shortest paths from the start to `goal`.
+    pub fn count_shortest_paths(&self, goal: &N) -> u64 {
+        if !self.distances.contains_key(goal) {
+            return 0;
+        }
+
+        count_paths(
+            goal.clone(),
+            |node| self.predecessors.get(node).cloned().unwrap_or_default(),
+            |node| *node == self.start,
+        )
+    }
+}
+
+/// Explore all nodes reachable from `start` in breadth-first order, where every edge has a length of 1.
+pub fn bfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> Paths<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut paths = Paths::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = paths.distances[&node] + 1;
+
+        for next in successors(&node) {
+            match paths.distances.get(&next) {
+                None => {
+                    paths.distances.insert(next.clone(), distance);
+                    paths.predecessors.insert(next.clone(), vec![node.clone()]);
+                    queue.push_back(next);
+                }
+                Some(d) if *d == distance => {
+                    paths
+                        .predecessors
+                        .entry(next)
+                        .or_default()
+                        .push(node.clone());
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    paths
+}
+
+/// The nodes reachable from `start` in depth-first order, starting with `start` itself.
+pub fn dfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> Vec<N>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visited: HashSet<N> = HashSet::new();
+    let mut order = vec![];
+    let mut stack = vec![start];
+
+    while let Some(node) = stack.pop() {
+        if !visited.insert(node.clone()) {
+            continue;
+        }
+
+        let mut next: Vec<N> = successors(&node)
+            .into_iter()
+            .filter(|n| !visited.contains(n))
+            .collect();
+        // visit successors in the order they were produced.
+        next.reverse();
+        stack.extend(next);
+        order.push(node);
+    }
+
+    order
+}
+
+/// A node on the open list of a search, ordered so that the `BinaryHeap` pops the lowest `priority` first.
+struct Open<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Open<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Open<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Open<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Open<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Explore all nodes reachable from `start` in order of their distance, where `successors` yields the neighbors
+/// of a node with the cost of the edge to them. Costs must not be negative; `C::default()` is the zero cost.
+/// With edges of zero cost, nodes can be each other's predecessors, in which case there are infinitely many shortest
+/// paths and [`Paths::count_shortest_paths`] panics. The start never has predecessors.
+pub fn dijkstra<N, C, I>(start: N, mut successors: impl FnMut(&N) -> I) -> Paths<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths::new(start.clone(), C::default());
+    let mut open = BinaryHeap::from([Open {
+        priority: C::default(),
+        cost: C::default(),
+        node: start,
+    }]);
+
+    while let Some(Open { cost, node, .. }) = open.pop() {
+        // skip stale entries of nodes that were reached more cheaply in the meantime.
+        if paths.distances.get(&node).is_some_and(|d| *d < cost) {
+            continue;
+        }
+
+        for (next, edge) in successors(&node) {
+            let next_cost = cost + edge;
+
+            match paths.distances.get(&next) {
+                Some(d) if *d < next_cost => {}
+                // a zero-cost edge back to the start, or to the node itself, doesn't lead anywhere new.
+                Some(_) if next == paths.start || next == node => {}
+                Some(d) if *d == next_cost => {
+                    let predecessors = paths.predecessors.entry(next).or_default();
+                    if !predecessors.contains(&node) {
+                        predecessors.push(node.clone());
+                    }
+                }
+                _ => {
+                    paths.distances.insert(next.clone(), next_cost);
+                    paths.predecessors.insert(next.clone(), vec![node.clone()]);
+                    open.push(Open {
+                        priority: next_cost,
+                        cost: next_cost,
+                        node: next,
+                    });
+                }
+            }
+        }
+    }
+
+    paths
+}
+
+/// Find a cheapest path from `start` to a node for which `is_goal` holds, guided by a `heuristic` that must never
+/// overestimate the remaining cost. Returns the path, including start and goal, and its cost.
+/// With a heuristic of `|_| 0`, this is Dijkstra's algorithm that stops at the first goal.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut paths = Paths::new(start.clone(), C::default());
+    let mut open = BinaryHeap::from([Open {
+        priority: heuristic(&start),
+        cost: C::default(),
+        node: start,
+    }]);
+
+    while let Some(Open { cost, node, .. }) = open.pop() {
+        if paths.distances.get(&node).is_some_and(|d| *d < cost) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            return paths.path_to(&node).map(|path| (path, cost));
+        }
+
+        for (next, edge) in successors(&node) {
+            let next_cost = cost + edge;
+
+            if paths.distances.get(&next).is_some_and(|d| *d <= next_cost) {
+                continue;
+            }
+
+            paths.distances.insert(next.clone(), next_cost);
+            paths.predecessors.insert(next.clone(), vec![node.clone()]);
+            open.push(Open {
+                priority: next_cost + heuristic(&next),
+                cost: next_cost,
+                node: next,
+            });
+        }
+    }
+
+    None
+}
+
+/// Group `nodes` into connected components, e.g. the regions of a map, where `successors` yields the neighbors
+/// that belong to the same component as a node. Components are returned in the order their first node appears in
+/// `nodes`, each in depth-first order.
+pub fn connected_components<N, I>(
+    nodes: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+) -> Vec<Vec<N>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen: HashSet<N> = HashSet::new();
+    let mut components = vec![];
+
+    for node in nodes {
+        if seen.contains(&node) {
+            continue;
+        }
+
+        let component = dfs(node, &mut successors);
+        seen.extend(component.iter().cloned());
+        components.push(component);
+    }
+
+    components
+}
+
+/// Count the distinct paths from `start` to nodes for which `is_goal` holds, memoizing the count per node.
+/// The graph must not have cycles, which would make the number of paths infinite, and panics if it finds one.
+/// A path ends at the first goal it reaches. Walks the graph with an explicit stack, so long paths can't overflow the
+/// call stack.
+pub fn count_paths<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> u64
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    // `None` marks the nodes whose paths are being counted, i.e. the ones on the stack.
+    let mut memo: HashMap<N, Option<u64>> = HashMap::new();
+    // the nodes on the current path, with their successors that are left to count and the paths counted so far.
+    let mut stack: Vec<(N, std::vec::IntoIter<N>, u64)> = vec![];
+    let mut next = Some(start);
+
+    loop {
+        let counted = match next.take() {
+            Some(node) if is_goal(&node) => Some(1),
+            Some(node) => match memo.get(&node) {
+                Some(Some(paths)) => Some(*paths),
+                Some(None) => {
+                    panic!("count_paths found a cycle, so there are infinitely many paths.")
+                }
+                None => {
+                    memo.insert(node.clone(), None);
+                    let successors: Vec<N> = successors(&node).into_iter().collect();
+                    stack.push((node, successors.into_iter(), 0));
+                    None
+                }
+            },
+            None => {
+                let (_, remaining, _) = stack.last_mut().unwrap();
+                next = remaining.next();
+                if next.is_some() {
+                    continue;
+                }
+
+                let (node, _, paths) = stack.pop().unwrap();
+                memo.insert(node, Some(paths));
+                Some(paths)
+            }
+        };
+
+        if let Some(paths) = counted {
+            match stack.last_mut() {
+                Some((_, _, total)) => *total += paths,
+                None => return paths,
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{astar, bfs, connected_components, count_paths, dfs, dijkstra};
+    use crate::grid::{Coord, Grid};
+
+    const MAZE: &str = "\
+S..#
+.#..
+...E";
+
+    fn maze() -> Grid<char> {
+        MAZE.parse().unwrap()
+    }
+
+    fn open_neighbors(grid: &Grid<char>, c: Coord) -> Vec<Coord> {
+        grid.neighbors4(c).filter(|n| grid[*n] != '#').collect()
+    }
+
+    #[test]
+    fn finds_shortest_paths_with_bfs() {
+        let grid = maze();
+        let start = grid.position(&'S').unwrap();
+        let end = grid.position(&'E').unwrap();
+        let paths = bfs(start, |c| open_neighbors(&grid, *c));
+
+        assert_eq!(paths.distance(&end), Some(5));
+        let path = paths.path_to(&end).unwrap();
+        assert_eq!(path.len(), 6);
+        assert_eq!(path.first(), Some(&start));
+        assert_eq!(path.last(), Some(&end));
+
+        // around the wall at (1, 1) either way.
+        assert_eq!(paths.count_shortest_paths(&end), 3);
+        assert_eq!(paths.nodes_on_shortest_paths(&end).len(), 10);
+        assert_eq!(paths.path_to(&Coord::new(3, 0)), None);
+    }
+
+    #[test]
+    fn visits_depth_first() {
+        let order = dfs(1, |n| {
+            if *n < 4 {
+                vec![n * 2, n * 2 + 1]
+            } else {
+                vec![]
+            }
+        });
+        assert_eq!(order, vec![1, 2, 4, 5, 3, 6, 7]);
+    }
+
+    #[test]
+    fn finds_cheapest_paths_with_dijkstra() {
+        // going straight is cheap, the shortcut via 2 is expensive.
+        let edges = |n: &u8| match n {
+            0 => vec![(1, 1), (2, 10)],
+            1 => vec![(3, 1)],
+            2 => vec![(3, 1)],
+            3 => vec![(4, 2)],
+            _ => vec![],
+        };
+
+        let paths = dijkstra(0, edges);
+        assert_eq!(paths.distance(&4), Some(4));
+        assert_eq!(paths.path_to(&4), Some(vec![0, 1, 3, 4]));
+        assert_eq!(paths.distance(&2), Some(10));
+
+        let ties = dijkstra(0u8, |n| match n {
+            0 => vec![(1, 1), (2, 1)],
+            1 | 2 => vec![(3, 1u32)],
+            _ => vec![],
+        });
+        assert_eq!(
+            ties.nodes_on_shortest_paths(&3),
+            HashSet::from([0, 1, 2, 3])
+        );
+        assert_eq!(ties.count_shortest_paths(&3), 2);
+    }
+
+    #[test]
+    fn handles_zero_cost_edges() {
+        let paths = dijkstra(0u8, |n| match n {
+            0 => vec![(0, 0), (1, 0u32)],
+            1 => vec![(0, 0), (2, 1)],
+            _ => vec![],
+        });
+        assert_eq!(paths.predecessors.get(&0), None);
+        assert_eq!(paths.path_to(&2), Some(vec![0, 1, 2]));
+        assert_eq!(paths.count_shortest_paths(&2), 1);
+        assert_eq!(paths.nodes_on_shortest_paths(&2), HashSet::from([0, 1, 2]));
+    }
+
+    #[test]
+    fn counts_paths_along_long_chains() {
+        let length = 100_000;
+        let paths = bfs(0, |n| if *n < length { vec![n + 1] } else { vec![] });
+        assert_eq!(paths.count_shortest_paths(&length), 1);
+        assert_eq!(count_paths(0, |n| vec![n + 1], |n| *n == length), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_infinitely_many_paths() {
+        count_paths(0, |n| vec![(n + 1) % 3], |n| *n == 5);
+    }
+
+    #[test]
+    fn finds_paths_with_astar() {
+        let grid = maze();
+        let start = grid.position(&'S').unwrap();
+        let end = grid.position(&'E').unwrap();
+        let manhattan = |c: &Coord| c.x.abs_diff(end.x) + c.y.abs_diff(end.y);
+
+        let (path, cost) = astar(
+            start,
+            |c| open_neighbors(&grid, *c).into_iter().map(|n| (n, 1)),
+            manhattan,
+            |c| *c == end,
+        )
+        .unwrap();
+        assert_eq!(cost, 5);
+        assert_eq!(path.len(), 6);
+
+        assert_eq!(astar(0, |_| vec![], |_| 0, |n| *n == 1), None);
+    }
+
+    #[test]
+    fn groups_connected_components() {
+        let grid: Grid<char> = "AAB\nABB\nCCB".parse().unwrap();
+        let regions = connected_components(grid.coords(), |c| {
+            grid.neighbors4(*c)
+                .filter(|n| grid[*n] == grid[*c])
+                .collect::<Vec<_>>()
+        });
+        let sizes: Vec<usize> = regions.iter().map(Vec::len).collect();
+        assert_eq!(sizes, vec![3, 4, 2]);
+    }
+
+    #[test]
+    fn counts_paths() {
+        let grid = Grid::parse_digits("0123\n1234\n2345").unwrap();
+        let trailhead = Coord::new(0, 0);
+        let paths = count_paths(
+            trailhead,
+            |c| {
+                grid.neighbors4(*c)
+                    .filter(|n| grid[*n] == grid[*c] + 1)
+                    .collect::<Vec<_>>()
+            },
+            |c| grid[*c] == 5,
+        );
+        // all monotone paths through a 3x4 lattice.
+        assert_eq!(paths, 10);
+    }
+}
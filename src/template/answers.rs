@@ -0,0 +1,351 @@
+/// Known-correct answers that results are verified against.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, PartRecord, Year};
+
+fn get_path(year: Year) -> String {
+    format!("./data/{year}/answers.json")
+}
+
+/// Represents the known-correct answers for a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Represents the known-correct answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+/// A part whose result does not match its recorded answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    pub day: Day,
+    pub part: u8,
+    pub expected: String,
+    /// The result of the part, or `None` if it returned no answer.
+    pub actual: Option<String>,
+}
+
+/// The outcome of checking a result against the known-correct answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// No answer has been recorded for this part.
+    Unknown,
+    Correct,
+    Incorrect {
+        expected: String,
+    },
+}
+
+impl Answers {
+    /// Dehydrate answers to the JSON file of a year.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(get_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from the JSON file of a year. If not present, returns empty answers.
+    /// Fails if the file can't be read or is malformed, so that it is never replaced by empty answers.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = get_path(year);
+        match fs::read_to_string(&path) {
+            Ok(json) => {
+                Answers::try_from(json).map_err(|e| format!("\"{path}\" is malformed: {e}"))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("could not read \"{path}\": {e}")),
+        }
+    }
+
+    /// Returns the recorded answer for a part, if any.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Record `value` as the correct answer for a part, replacing a previously recorded answer.
+    pub fn set(&mut self, day: Day, part: u8, value: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let answer = &mut self.data[index];
+        match part {
+            1 => answer.part_1 = Some(value.into()),
+            2 => answer.part_2 = Some(value.into()),
+            _ => {}
+        }
+    }
+
+    /// Check a result against the recorded answer for a part.
+    pub fn verify(&self, day: Day, part: u8, result: Option<&str>) -> Verdict {
+        match self.get(day, part) {
+            None => Verdict::Unknown,
+            Some(expected) if Some(expected) == result => Verdict::Correct,
+            Some(expected) => Verdict::Incorrect {
+                expected: expected.into(),
+            },
+        }
+    }
+
+    /// Check the recorded answers of every day in `records`, returning the parts that did not produce them.
+    /// Days without records (e.g. because their input is missing) are not checked.
+    pub fn mismatches(&self, records: &[PartRecord]) -> Vec<Mismatch> {
+        let mut mismatches: Vec<Mismatch> = vec![];
+
+        for answer in &self.data {
+            if !records.iter().any(|r| r.day == answer.day) {
+                continue;
+            }
+
+            for part in [1, 2] {
+                let actual = records
+                    .iter()
+                    .find(|r| r.day == answer.day && r.part == part)
+                    .and_then(|r| r.answer.clone());
+
+                if let Verdict::Incorrect { expected } =
+                    self.verify(answer.day, part, actual.as_deref())
+                {
+                    mismatches.push(Mismatch {
+                        day: answer.day,
+                        part,
+                        expected,
+                        actual,
+                    });
+                }
+            }
+        }
+
+        mismatches
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        for (key, answer) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match answer {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Mismatch, Verdict};
+    use crate::{
+        day,
+        template::{BenchStats, PartRecord},
+        year,
+    };
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": "42", "part_2": null }] }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(day!(1), 1), Some("42"));
+        assert_eq!(answers.get(day!(1), 2), None);
+        assert_eq!(answers.get(day!(2), 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "01", "part_1": 42 }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(3), 2, "x");
+        answers.set(day!(1), 1, "y");
+        answers.set(day!(3), 2, "z");
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.get(day!(3), 2), Some("z"));
+    }
+
+    #[test]
+    fn verifies_results() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "42");
+        assert_eq!(answers.verify(day!(1), 1, Some("42")), Verdict::Correct);
+        assert_eq!(
+            answers.verify(day!(1), 1, Some("41")),
+            Verdict::Incorrect {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(
+            answers.verify(day!(1), 1, None),
+            Verdict::Incorrect {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(answers.verify(day!(1), 2, Some("42")), Verdict::Unknown);
+    }
+
+    #[test]
+    fn finds_mismatches() {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "42");
+        answers.set(day!(1), 2, "43");
+        answers.set(day!(2), 1, "44");
+        answers.set(day!(3), 1, "45");
+
+        let record = |day, part: u8, answer: Option<&str>| PartRecord {
+            year: year!(2024),
+            day,
+            part,
+            answer: answer.map(Into::into),
+            stats: BenchStats::default(),
+            timed_out: false,
+            error: None,
+        };
+
+        let mismatches = answers.mismatches(&[
+            record(day!(1), 1, Some("42")),
+            record(day!(1), 2, Some("0")),
+            record(day!(2), 1, None),
+        ]);
+        assert_eq!(
+            mismatches,
+            vec![
+                Mismatch {
+                    day: day!(1),
+                    part: 2,
+                    expected: "43".into(),
+                    actual: Some("0".into()),
+                },
+                Mismatch {
+                    day: day!(2),
+                    part: 1,
+                    expected: "44".into(),
+                    actual: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trips_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(5), 1, "6267");
+        let json = tinyjson::JsonValue::from(answers.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+}
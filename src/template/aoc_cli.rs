@@ -1,125 +1,236 @@
 /// Wrapper module around the "aoc-cli" command-line.
-use std::{
-    fmt::Display,
-    process::{Command, Output, Stdio},
-};
+/// With the `native-client` feature, requests are made by [`aoc_client`](crate::template::aoc_client) instead.
+/// Either way, every request to adventofcode.com is spaced out by [`fetch_log::throttled`].
+use std::{fmt::Display, fs, io, path::Path, process::Output};
 
-use crate::template::Day;
+use crate::template::PuzzleId;
+
+#[cfg(not(feature = "native-client"))]
+pub use cli::{check, download, read, submit};
+#[cfg(feature = "native-client")]
+pub use native::{check, download, read, submit};
 
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
     BadExitStatus(Output),
+    WriteFailed(io::Error),
+    #[cfg(feature = "native-client")]
+    Client(crate::template::aoc_client::ClientError),
 }
 
 impl Display for AocCommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
+            AocCommandError::CommandNotFound => write!(f, "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."),
             AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
+            AocCommandError::WriteFailed(e) => write!(f, "failed to write file: {e}"),
+            #[cfg(feature = "native-client")]
+            AocCommandError::Client(e) => write!(f, "{e}"),
         }
     }
 }
 
-pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
-        .arg("-V")
-        .output()
-        .map_err(|_| AocCommandError::CommandNotFound)?;
-    Ok(())
-}
+#[cfg(not(feature = "native-client"))]
+mod cli {
+    use std::process::{Command, Output, Stdio};
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+    use super::{create_parent_dir, get_input_path, get_puzzle_path, AocCommandError};
+    use crate::template::{fetch_log, PuzzleId};
 
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
+    pub fn check() -> Result<(), AocCommandError> {
+        Command::new("aoc")
+            .arg("-V")
+            .output()
+            .map_err(|_| AocCommandError::CommandNotFound)?;
+        Ok(())
+    }
 
-    call_aoc_cli(&args)
-}
+    pub fn read(puzzle: PuzzleId) -> Result<(), AocCommandError> {
+        let puzzle_path = get_puzzle_path(puzzle);
+        create_parent_dir(&puzzle_path)?;
+
+        let args = build_args(
+            "read",
+            &[
+                "--description-only".into(),
+                "--puzzle-file".into(),
+                puzzle_path,
+            ],
+            puzzle,
+        );
+
+        fetch_log::throttled(puzzle, || call_aoc_cli(&args))?;
+        Ok(())
+    }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
-
-    let args = build_args(
-        "download",
-        &[
-            "--overwrite".into(),
-            "--input-file".into(),
-            input_path.to_string(),
-            "--puzzle-file".into(),
-            puzzle_path.to_string(),
-        ],
-        day,
-    );
-
-    let output = call_aoc_cli(&args)?;
-    println!("---");
-    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
-    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
-    Ok(output)
-}
+    pub fn download(puzzle: PuzzleId) -> Result<(), AocCommandError> {
+        let input_path = get_input_path(puzzle);
+        let puzzle_path = get_puzzle_path(puzzle);
+        create_parent_dir(&input_path)?;
+        create_parent_dir(&puzzle_path)?;
+
+        // one request per call, so that each of them is throttled.
+        let input_args = build_args(
+            "download",
+            &[
+                "--overwrite".into(),
+                "--input-only".into(),
+                "--input-file".into(),
+                input_path.to_string(),
+            ],
+            puzzle,
+        );
+        let puzzle_args = build_args(
+            "download",
+            &[
+                "--overwrite".into(),
+                "--puzzle-only".into(),
+                "--puzzle-file".into(),
+                puzzle_path.to_string(),
+            ],
+            puzzle,
+        );
+
+        fetch_log::throttled(puzzle, || call_aoc_cli(&input_args))?;
+        fetch_log::throttled(puzzle, || call_aoc_cli(&puzzle_args))?;
+        println!("---");
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        Ok(())
+    }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
-    // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
-    args.push(part.to_string());
-    args.push(result.to_string());
-    call_aoc_cli(&args)
-}
+    /// Submit an answer. The output of aoc-cli is echoed and also returned, so the verdict can be parsed from it.
+    pub fn submit(puzzle: PuzzleId, part: u8, result: &str) -> Result<String, AocCommandError> {
+        // workaround: the argument order is inverted for submit.
+        let mut args = build_args("submit", &[], puzzle);
+        args.push(part.to_string());
+        args.push(result.to_string());
+
+        let output = Command::new("aoc")
+            .args(&args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .output()
+            .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        print!("{stdout}");
+
+        if output.status.success() {
+            Ok(stdout)
+        } else {
+            Err(AocCommandError::BadExitStatus(output))
+        }
+    }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
-}
+    fn build_args(command: &str, args: &[String], puzzle: PuzzleId) -> Vec<String> {
+        let mut cmd_args = args.to_vec();
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
-}
+        cmd_args.append(&mut vec![
+            "--year".into(),
+            puzzle.year.to_string(),
+            "--day".into(),
+            puzzle.day.to_string(),
+            command.into(),
+        ]);
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
+        cmd_args
+    }
+
+    fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+        // println!("Calling >aoc with: {}", args.join(" "));
+        let output = Command::new("aoc")
+            .args(args)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .output()
+            .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+        if output.status.success() {
+            Ok(output)
+        } else {
+            Err(AocCommandError::BadExitStatus(output))
+        }
     }
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
-    let mut cmd_args = args.to_vec();
+#[cfg(feature = "native-client")]
+mod native {
+    use std::fs;
+
+    use super::{create_parent_dir, get_input_path, get_puzzle_path, AocCommandError};
+    use crate::template::{aoc_client, aoc_client::Client, fetch_log, PuzzleId, Year};
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
+    fn client(year: Year) -> Result<Client, AocCommandError> {
+        Client::from_env(year).map_err(AocCommandError::Client)
     }
 
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+    pub fn check() -> Result<(), AocCommandError> {
+        aoc_client::read_session()
+            .map(|_| ())
+            .map_err(AocCommandError::Client)
+    }
+
+    pub fn read(puzzle: PuzzleId) -> Result<(), AocCommandError> {
+        let puzzle_path = get_puzzle_path(puzzle);
+        let client = client(puzzle.year)?;
+        let description = fetch_log::throttled(puzzle, || client.get_puzzle(puzzle.day))
+            .map_err(AocCommandError::Client)?;
+
+        create_parent_dir(&puzzle_path)?;
+        fs::write(&puzzle_path, &description).map_err(AocCommandError::WriteFailed)?;
+        println!("{description}");
+        Ok(())
+    }
+
+    pub fn download(puzzle: PuzzleId) -> Result<(), AocCommandError> {
+        let input_path = get_input_path(puzzle);
+        let puzzle_path = get_puzzle_path(puzzle);
+
+        let client = client(puzzle.year)?;
+        let input = fetch_log::throttled(puzzle, || client.get_input(puzzle.day))
+            .map_err(AocCommandError::Client)?;
+        let description = fetch_log::throttled(puzzle, || client.get_puzzle(puzzle.day))
+            .map_err(AocCommandError::Client)?;
+
+        create_parent_dir(&input_path)?;
+        create_parent_dir(&puzzle_path)?;
+        fs::write(&input_path, input).map_err(AocCommandError::WriteFailed)?;
+        fs::write(&puzzle_path, description).map_err(AocCommandError::WriteFailed)?;
+
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        Ok(())
+    }
+
+    /// Submit an answer. The message of the server is printed and also returned, so the verdict can be parsed from it.
+    pub fn submit(puzzle: PuzzleId, part: u8, result: &str) -> Result<String, AocCommandError> {
+        let message = client(puzzle.year)?
+            .submit(puzzle.day, part, result)
+            .map_err(AocCommandError::Client)?;
+
+        print!("{message}");
+        Ok(message)
+    }
+}
+
+fn get_input_path(puzzle: PuzzleId) -> String {
+    puzzle.data_path("inputs", "txt")
+}
 
-    cmd_args
+fn get_puzzle_path(puzzle: PuzzleId) -> String {
+    puzzle.data_path("puzzles", "md")
 }
 
-fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
-    // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
-        .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .output()
-        .map_err(|_| AocCommandError::CommandNotCallable)?;
-
-    if output.status.success() {
-        Ok(output)
-    } else {
-        Err(AocCommandError::BadExitStatus(output))
+/// Create the (year-namespaced) directory of a data file if it does not exist yet.
+fn create_parent_dir(path: &str) -> Result<(), AocCommandError> {
+    match Path::new(path).parent() {
+        Some(dir) => fs::create_dir_all(dir).map_err(AocCommandError::WriteFailed),
+        None => Ok(()),
     }
 }
@@ -0,0 +1,338 @@
+/// Native HTTP client for adventofcode.com, used instead of aoc-cli when the `native-client` feature is enabled.
+use std::{env, fmt::Display, fs, path::PathBuf, sync::OnceLock};
+
+use regex::{Captures, Regex};
+
+use crate::template::{Day, Year};
+
+/// Environment variable that overrides the base URL, e.g. to point the client at a local stand-in server.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+/// Environment variable that holds the session cookie. Takes precedence over the session file.
+pub const SESSION_ENV: &str = "ADVENT_OF_CODE_SESSION";
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static SESSION_FILE_NAME: &str = ".adventofcode.session";
+static USER_AGENT: &str = concat!(
+    "github.com/fspoettel/advent-of-code-rust advent_of_code/",
+    env!("CARGO_PKG_VERSION")
+);
+
+#[derive(Debug)]
+pub enum ClientError {
+    SessionNotFound,
+    /// The request failed or the server responded with an error status.
+    Http(String),
+    /// The response did not contain the expected content.
+    UnexpectedResponse(String),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::SessionNotFound => write!(
+                f,
+                "session cookie not found. Set ${SESSION_ENV} or create the file ~/{SESSION_FILE_NAME}."
+            ),
+            ClientError::Http(e) => write!(f, "request failed: {e}"),
+            ClientError::UnexpectedResponse(e) => write!(f, "unexpected response: {e}"),
+        }
+    }
+}
+
+/// A client for the puzzles of a single year.
+pub struct Client {
+    base_url: String,
+    session: String,
+    year: Year,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    /// Configure a client for `year` from the environment: the session cookie and, optionally, [`BASE_URL_ENV`].
+    pub fn from_env(year: Year) -> Result<Self, ClientError> {
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+
+        Ok(Self::new(&base_url, &read_session()?, year))
+    }
+
+    pub fn new(base_url: &str, session: &str, year: Year) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.into(),
+            year,
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Fetch the puzzle input of a day.
+    pub fn get_input(&self, day: Day) -> Result<String, ClientError> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    /// Fetch the description of a day, converted to markdown.
+    /// Contains the second part only if the first part has been solved.
+    pub fn get_puzzle(&self, day: Day) -> Result<String, ClientError> {
+        let html = self.get(&self.day_url(day))?;
+
+        let articles = articles(&html);
+        if articles.is_empty() {
+            return Err(ClientError::UnexpectedResponse(
+                "puzzle page contains no description.".into(),
+            ));
+        }
+
+        Ok(articles
+            .iter()
+            .map(|x| to_markdown(x))
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    /// Submit an answer, returning the message of the server as markdown.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<String, ClientError> {
+        let url = format!("{}/answer", self.day_url(day));
+
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        let html = read_response(&url, response)?;
+
+        articles(&html)
+            .first()
+            .map(|x| to_markdown(x))
+            .ok_or(ClientError::UnexpectedResponse(
+                "answer page contains no message.".into(),
+            ))
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn get(&self, url: &str) -> Result<String, ClientError> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        read_response(url, response)
+    }
+}
+
+fn read_response(
+    url: &str,
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<String, ClientError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| ClientError::Http(e.to_string())),
+        Err(ureq::Error::Status(code, _)) => {
+            Err(ClientError::Http(format!("{url} responded with {code}.")))
+        }
+        Err(e) => Err(ClientError::Http(e.to_string())),
+    }
+}
+
+/// Read the session cookie from [`SESSION_ENV`] or `~/.adventofcode.session`.
+pub fn read_session() -> Result<String, ClientError> {
+    if let Ok(session) = env::var(SESSION_ENV) {
+        return Ok(session.trim().into());
+    }
+
+    env::var_os("HOME")
+        .map(|home| PathBuf::from(home).join(SESSION_FILE_NAME))
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+        .ok_or(ClientError::SessionNotFound)
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn regex(cell: &'static OnceLock<Regex>, pattern: &str) -> &'static Regex {
+    cell.get_or_init(|| Regex::new(pattern).unwrap())
+}
+
+/// Extract the contents of all `<article>` elements of a page.
+fn articles(html: &str) -> Vec<&str> {
+    static ARTICLE: OnceLock<Regex> = OnceLock::new();
+    regex(&ARTICLE, r"(?s)<article[^>]*>(.*?)</article>")
+        .captures_iter(html)
+        .map(|c| c.get(1).unwrap().as_str())
+        .collect()
+}
+
+/// Convert the subset of HTML used in puzzle descriptions to markdown.
+fn to_markdown(html: &str) -> String {
+    static PRE: OnceLock<Regex> = OnceLock::new();
+    let pre = regex(&PRE, r"(?s)<pre><code>(.*?)</code></pre>");
+
+    let mut markdown = String::new();
+    let mut last = 0;
+
+    for captures in pre.captures_iter(html) {
+        let block = captures.get(0).unwrap();
+        markdown.push_str(&inline_to_markdown(&html[last..block.start()]));
+        markdown.push_str("```\n");
+        markdown.push_str(&decode_entities(&strip_tags(&captures[1])));
+        markdown.push_str("```\n\n");
+        last = block.end();
+    }
+    markdown.push_str(&inline_to_markdown(&html[last..]));
+
+    static BLANK_LINES: OnceLock<Regex> = OnceLock::new();
+    let markdown = regex(&BLANK_LINES, r"\n{3,}").replace_all(&markdown, "\n\n");
+
+    format!("{}\n", markdown.trim())
+}
+
+fn inline_to_markdown(html: &str) -> String {
+    static BETWEEN_TAGS: OnceLock<Regex> = OnceLock::new();
+    static HEADING: OnceLock<Regex> = OnceLock::new();
+    static LINK: OnceLock<Regex> = OnceLock::new();
+
+    // line breaks between block elements are not part of the text.
+    let html = regex(&BETWEEN_TAGS, r">\n+<").replace_all(html, "><");
+    let html = regex(&HEADING, r"(?s)<h2[^>]*>(.*?)</h2>").replace_all(&html, "## $1\n\n");
+    let html = regex(&LINK, r#"(?s)<a href="([^"]*)"[^>]*>(.*?)</a>"#)
+        .replace_all(&html, |c: &Captures| format!("[{}]({})", &c[2], &c[1]));
+
+    let html = html
+        .replace("</p>", "\n\n")
+        .replace("</ul>", "\n")
+        .replace("<li>", "- ")
+        .replace("</li>", "\n")
+        .replace("<code>", "`")
+        .replace("</code>", "`")
+        .replace("</em>", "*");
+
+    static EM: OnceLock<Regex> = OnceLock::new();
+    let html = regex(&EM, r"<em[^>]*>").replace_all(&html, "*");
+
+    decode_entities(&strip_tags(&html))
+}
+
+fn strip_tags(html: &str) -> String {
+    static TAG: OnceLock<Regex> = OnceLock::new();
+    regex(&TAG, r"<[^>]+>").replace_all(html, "").into()
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    use super::{articles, to_markdown, Client};
+    use crate::{day, year};
+
+    /// Serve a single request with `body`, returning the request line and headers that were received.
+    fn serve_once(body: &'static str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(length) = line.to_lowercase().strip_prefix("content-length: ") {
+                    content_length = length.trim().parse().unwrap();
+                }
+                if line == "\r\n" {
+                    break;
+                }
+                request.push_str(&line);
+            }
+
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push_str(&String::from_utf8(content).unwrap());
+
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn converts_puzzles_to_markdown() {
+        let html = r#"<h2>--- Day 1: Test ---</h2><p>Find the <em>sum</em> of <code>a &lt; b</code>, see <a href="/2024/about">here</a>.</p>
+<pre><code>1 <em>2</em>
+3
+</code></pre>
+<ul>
+<li>one</li>
+<li>two</li>
+</ul>"#;
+
+        assert_eq!(
+            to_markdown(html),
+            "## --- Day 1: Test ---\n\nFind the *sum* of `a < b`, see [here](/2024/about).\n\n```\n1 2\n3\n```\n\n- one\n- two\n"
+        );
+    }
+
+    #[test]
+    fn extracts_articles() {
+        let html = r#"<main><article class="day-desc"><p>a</p></article><p>b</p><article class="day-desc"><p>c</p></article></main>"#;
+        assert_eq!(articles(html), vec!["<p>a</p>", "<p>c</p>"]);
+    }
+
+    #[test]
+    fn fetches_inputs_with_session() {
+        let (url, handle) = serve_once("1\n2\n");
+        let client = Client::new(&url, "secret", year!(2024));
+
+        assert_eq!(client.get_input(day!(3)).unwrap(), "1\n2\n");
+
+        let request = handle.join().unwrap();
+        assert_eq!(request.starts_with("GET /2024/day/3/input "), true);
+        assert_eq!(request.contains("session=secret"), true);
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, handle) =
+            serve_once("<main><article><p>That's the right answer!</p></article></main>");
+        let client = Client::new(&url, "secret", year!(2024));
+
+        assert_eq!(
+            client.submit(day!(3), 2, "42").unwrap(),
+            "That's the right answer!\n"
+        );
+
+        let request = handle.join().unwrap();
+        assert_eq!(request.starts_with("POST /2024/day/3/answer "), true);
+        assert_eq!(request.ends_with("level=2&answer=42"), true);
+    }
+}
@@ -1,5 +1,135 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::collections::HashMap;
+use std::process;
+use std::sync::Arc;
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::answers::{Answers, Mismatch};
+use crate::template::outcome::{self, DayOutcome, DayReport};
+use crate::template::{
+    all_days,
+    run_multi::{read_answers, run_multi},
+    PartRecord, PuzzleId, Solution, Year, ANSI_BOLD, ANSI_RESET,
+};
+
+pub fn handle(
+    solutions: &[Solution],
+    years: &[Year],
+    verify: bool,
+    timeout: Option<Duration>,
+    jobs: usize,
+    failed_to_compile: &[PuzzleId],
+) {
+    let puzzles = years
+        .iter()
+        .flat_map(|year| all_days().map(|day| PuzzleId::new(*year, day)))
+        .filter(|puzzle| !failed_to_compile.contains(puzzle))
+        .collect();
+
+    let answers = read_answers(years);
+    let mut reports = run_multi(solutions, &puzzles, &answers, None, timeout, jobs);
+
+    reports.extend(
+        failed_to_compile
+            .iter()
+            .filter(|puzzle| years.contains(&puzzle.year))
+            .map(|puzzle| DayReport {
+                puzzle: *puzzle,
+                outcome: DayOutcome::FailedToCompile,
+                records: vec![],
+            }),
+    );
+    reports.sort_by_key(|report| report.puzzle);
+
+    let mut has_failures = false;
+
+    if verify {
+        has_failures |= !verify_answers(years, &answers, &reports);
+    }
+
+    println!();
+    outcome::print_summary(&reports);
+
+    let failed_days = reports.iter().filter(|r| r.outcome.is_failure()).count();
+
+    if failed_days > 0 {
+        eprintln!("{failed_days} day(s) failed, panicked, timed out or failed to compile.");
+        has_failures = true;
+    }
+
+    if has_failures {
+        process::exit(1);
+    }
+}
+
+/// Print the parts whose result differs from the recorded answer. Returns whether all recorded answers match.
+/// Years without `answers`, as their answers file is malformed, can't be verified.
+fn verify_answers(
+    years: &[Year],
+    answers: &HashMap<Year, Arc<Answers>>,
+    reports: &[DayReport],
+) -> bool {
+    let records: Vec<&PartRecord> = reports.iter().flat_map(|r| &r.records).collect();
+
+    let mut mismatches: Vec<(Year, Mismatch)> = vec![];
+    let mut errors: Vec<String> = vec![];
+
+    for year in years {
+        // the error was reported when the answers were read.
+        let Some(answers) = answers.get(year) else {
+            errors.push(format!(
+                "Could not verify the answers of {year}, see above."
+            ));
+            continue;
+        };
+
+        let year_records: Vec<PartRecord> = records
+            .iter()
+            .filter(|r| r.year == *year)
+            .map(|r| (*r).clone())
+            .collect();
+
+        mismatches.extend(
+            answers
+                .mismatches(&year_records)
+                .into_iter()
+                .map(|mismatch| (*year, mismatch)),
+        );
+    }
+
+    println!();
+    println!("{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------");
+
+    for error in &errors {
+        eprintln!("{error}");
+    }
+
+    if mismatches.is_empty() && errors.is_empty() {
+        println!("All recorded answers match.");
+        return true;
+    }
+
+    for (year, mismatch) in &mismatches {
+        let timed_out = records.iter().any(|r| {
+            r.year == *year && r.day == mismatch.day && r.part == mismatch.part && r.timed_out
+        });
+        let no_answer = if timed_out {
+            "⏱ timed out"
+        } else {
+            "no answer"
+        };
+
+        println!(
+            "Day {} ({year}) part {}: expected {}, got {}",
+            mismatch.day,
+            mismatch.part,
+            mismatch.expected,
+            mismatch.actual.as_deref().unwrap_or(no_answer),
+        );
+    }
+
+    if !mismatches.is_empty() {
+        eprintln!("{} recorded answer(s) did not match.", mismatches.len());
+    }
+    false
 }
@@ -1,14 +1,26 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{aoc_cli, PuzzleId};
+use std::{fs, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+/// Download the input and puzzle of a day, unless the input has been downloaded before and `force` is not set.
+pub fn handle(puzzle: PuzzleId, force: bool) {
+    let input_path = puzzle.data_path("inputs", "txt");
+
+    let is_cached = fs::metadata(&input_path).is_ok_and(|m| m.len() > 0);
+    if is_cached && !force {
+        println!(
+            "🎄 Input for day {} is already downloaded. Pass `--force` to download it again.",
+            puzzle.day
+        );
+        return;
+    }
+
+    if let Err(e) = aoc_cli::check() {
+        eprintln!("{e}");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    if let Err(e) = aoc_cli::download(puzzle) {
+        eprintln!("failed to download: {e}");
         process::exit(1);
     };
 }
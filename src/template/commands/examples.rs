@@ -0,0 +1,118 @@
+use std::{fs, process};
+
+use crate::template::examples::{ExampleAnswers, PuzzleExamples};
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_RESET};
+
+/// Lines of a code block shown by `--list`.
+const PREVIEW_LINES: usize = 3;
+
+/// Extract the code blocks `blocks` (1-based, default: the first) of a downloaded puzzle description into the examples
+/// of a day, and store the detected example answers next to them. A single block is written to `NN.txt`, multiple
+/// blocks to `NN-1.txt`, `NN-2.txt`, ... to be read with `read_file_part`.
+pub fn handle(puzzle: PuzzleId, blocks: &[usize], list: bool, overwrite: bool) {
+    let puzzle_path = puzzle.data_path("puzzles", "md");
+
+    let Ok(markdown) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "Puzzle description \"{puzzle_path}\" not found. Download it with `cargo download {}` first.",
+            puzzle.day
+        );
+        process::exit(1);
+    };
+
+    let examples = PuzzleExamples::parse(&markdown);
+
+    if examples.blocks.is_empty() {
+        eprintln!("Puzzle description \"{puzzle_path}\" contains no code blocks.");
+        process::exit(1);
+    }
+
+    if list {
+        print_blocks(&examples);
+        return;
+    }
+
+    let blocks = if blocks.is_empty() { &[1] } else { blocks };
+
+    if let Some(block) = blocks
+        .iter()
+        .find(|&&b| b == 0 || b > examples.blocks.len())
+    {
+        eprintln!(
+            "Code block {block} does not exist. The puzzle description contains {} code block(s), see `--list`.",
+            examples.blocks.len()
+        );
+        process::exit(1);
+    }
+
+    for (i, block) in blocks.iter().enumerate() {
+        let path = if blocks.len() == 1 {
+            puzzle.data_path("examples", "txt")
+        } else {
+            puzzle.data_part_path("examples", u8::try_from(i + 1).unwrap_or(u8::MAX), "txt")
+        };
+
+        let content = &examples.blocks[block - 1].content;
+        write_file("example", &path, content, overwrite);
+    }
+
+    if examples.answers.is_empty() {
+        println!("No example answers detected.");
+        return;
+    }
+
+    // answers that were corrected by hand are only replaced with `--overwrite`, like the examples.
+    let answers_path = ExampleAnswers::get_path(puzzle);
+    if write_file(
+        "example answers",
+        &answers_path,
+        &examples.answers.to_string(),
+        overwrite,
+    ) {
+        print!("{}", examples.answers);
+    }
+}
+
+/// Write the `name` file at `path`, unless it has contents and `overwrite` is not set. Returns whether it was written.
+fn write_file(name: &str, path: &str, content: &str, overwrite: bool) -> bool {
+    let has_content = fs::metadata(path).is_ok_and(|m| m.len() > 0);
+    if has_content && !overwrite {
+        println!("Skipped \"{path}\" as it is not empty. Pass `--overwrite` to replace it.");
+        return false;
+    }
+
+    match fs::write(path, content) {
+        Ok(()) => {
+            println!("Wrote {name} to \"{path}\"");
+            true
+        }
+        Err(e) => {
+            eprintln!("Failed to write {name} file: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+fn print_blocks(examples: &PuzzleExamples) {
+    for (i, block) in examples.blocks.iter().enumerate() {
+        let lines: Vec<&str> = block.content.lines().collect();
+
+        println!(
+            "{ANSI_BOLD}[{}]{ANSI_RESET} part {}, {} line(s)",
+            i + 1,
+            block.part,
+            lines.len()
+        );
+        for line in lines.iter().take(PREVIEW_LINES) {
+            println!("    {line}");
+        }
+        if lines.len() > PREVIEW_LINES {
+            println!("    ...");
+        }
+    }
+
+    if !examples.answers.is_empty() {
+        println!("---");
+        print!("{}", examples.answers);
+    }
+}
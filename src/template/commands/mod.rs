@@ -1,6 +1,9 @@
 pub mod all;
 pub mod download;
+pub mod examples;
 pub mod read;
+pub mod registry;
 pub mod scaffold;
 pub mod solve;
+pub mod stress;
 pub mod time;
@@ -0,0 +1,84 @@
+use std::path::Path;
+use std::process::{self, Command, Stdio};
+
+use crate::template::solution_bins::{solution_bins, FAILED_TO_COMPILE_ENV};
+
+/// Build the `registry` binary, which contains every solution, and run the `all`, `time` or `stress` command in
+/// `args` with it. Exits with the status of the command.
+///
+/// If the registry doesn't compile, every solution is built on its own to find the ones that break it, and the
+/// registry is built without them. The command reports them as failed to compile.
+pub fn handle(args: &[String]) {
+    // build with the same profile as this binary, i.e. `--release` for the `cargo all` alias.
+    let profile: &[&str] = if cfg!(debug_assertions) {
+        &[]
+    } else {
+        &["--release"]
+    };
+
+    let mut failed_to_compile: Vec<String> = vec![];
+
+    if !cargo("build", profile, &failed_to_compile)
+        .status()
+        .unwrap()
+        .success()
+    {
+        failed_to_compile = solution_bins(Path::new("src/bin"))
+            .into_iter()
+            .filter(|bin| !builds_on_its_own(bin, profile))
+            .collect();
+
+        let registry_builds = !failed_to_compile.is_empty()
+            && cargo("build", profile, &failed_to_compile)
+                .status()
+                .unwrap()
+                .success();
+
+        if !registry_builds {
+            eprintln!("The `registry` binary failed to compile, see the errors above.");
+            process::exit(1);
+        }
+
+        eprintln!(
+            "Leaving out solutions that fail to compile: {}.",
+            failed_to_compile.join(", ")
+        );
+        eprintln!();
+    }
+
+    let status = cargo("run", profile, &failed_to_compile)
+        .arg("--")
+        .args(args)
+        .status()
+        .unwrap();
+
+    process::exit(status.code().unwrap_or(1));
+}
+
+/// A cargo command for the `registry` binary that leaves out `failed_to_compile`.
+fn cargo(command: &str, profile: &[&str], failed_to_compile: &[String]) -> Command {
+    let mut cmd = Command::new("cargo");
+    cmd.args([command, "--quiet", "--bin", "registry"])
+        .args(profile);
+
+    // an unchanged environment doesn't re-run `build.rs`, which would rebuild every binary.
+    if failed_to_compile.is_empty() {
+        cmd.env_remove(FAILED_TO_COMPILE_ENV);
+    } else {
+        cmd.env(FAILED_TO_COMPILE_ENV, failed_to_compile.join(","));
+    }
+
+    cmd
+}
+
+/// Whether the solution binary `bin` compiles. Its errors were already printed by the failed registry build.
+fn builds_on_its_own(bin: &str, profile: &[&str]) -> bool {
+    Command::new("cargo")
+        .args(["build", "--quiet", "--bin", bin])
+        .args(profile)
+        .env_remove(FAILED_TO_COMPILE_ENV)
+        .stderr(Stdio::null())
+        .status()
+        .unwrap()
+        .success()
+}
@@ -1,79 +1,505 @@
-use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
-};
+use std::{env, fs, io, path::Path, process};
 
-use crate::template::Day;
+use regex::{Captures, Regex};
+
+use crate::template::examples::ExampleAnswers;
+use crate::template::PuzzleId;
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
+/// Environment variable that selects the template used when `--template` is not passed.
+pub const TEMPLATE_ENV: &str = "AOC_TEMPLATE";
+
+static TEMPLATES_DIR: &str = "templates";
+
+/// Read the module template `name` from the `templates` directory, or the built-in template if no name is given.
+fn read_template(name: Option<&str>) -> Result<String, String> {
+    let Some(name) = name else {
+        return Ok(MODULE_TEMPLATE.into());
+    };
+
+    let path = Path::new(TEMPLATES_DIR).join(format!("{name}.txt"));
+    fs::read_to_string(&path).map_err(|e| format!("could not read \"{}\": {e}", path.display()))
+}
+
+/// Read the title from the first line of a downloaded puzzle description, e.g. `--- Day 1: Historian Hysteria ---`.
+fn puzzle_title(markdown: &str) -> Option<String> {
+    let line = markdown.lines().find(|line| line.contains("--- Day "))?;
+    let (_, title) = line.split_once(": ")?;
+    Some(title.trim_end_matches('-').trim().to_string())
+}
+
+/// Substitute the placeholders of a module template.
+fn render_template(
+    template: &str,
+    puzzle: PuzzleId,
+    title: Option<&str>,
+    answers: &ExampleAnswers,
+) -> String {
+    let day = puzzle.day.into_inner();
+    let title = title.map_or_else(|| format!("Day {day}"), ToString::to_string);
+
+    template
+        .replace("%DAY_NUMBER%", &day.to_string())
+        .replace("%YEAR%", &puzzle.year.to_string())
+        .replace("%TITLE%", &title)
+        .replace(
+            "%URL%",
+            &format!("https://adventofcode.com/{}/day/{day}", puzzle.year),
+        )
+        .replace(
+            "%PART_ONE_EXAMPLE%",
+            &expected_value(answers.part_one.as_deref()),
+        )
+        .replace(
+            "%PART_TWO_EXAMPLE%",
+            &expected_value(answers.part_two.as_deref()),
+        )
+}
+
+/// The generated files of a day that may be replaced if they already exist.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Overwrite {
+    pub module: bool,
+    pub input: bool,
+    pub example: bool,
+}
+
+/// What scaffolding does, or did, to a generated file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Outcome {
+    Created,
+    Kept {
+        is_empty: bool,
+    },
+    /// The file was replaced. Its previous contents, if any, are kept in `backup`.
+    Replaced {
+        backup: Option<String>,
+    },
+}
+
+/// A file generated by `scaffold`.
+struct Artifact {
+    name: &'static str,
+    flag: &'static str,
+    path: String,
+    contents: String,
+    outcome: Outcome,
+}
+
+/// Decide what to do with the file at `path`: existing files are only replaced if `overwrite` is set.
+fn plan(path: &str, overwrite: bool) -> Outcome {
+    let Ok(metadata) = fs::metadata(path) else {
+        return Outcome::Created;
+    };
+
+    let is_empty = metadata.len() == 0;
+    if !overwrite {
+        return Outcome::Kept { is_empty };
+    }
+
+    Outcome::Replaced {
+        backup: (!is_empty).then(|| backup_path(path)),
+    }
+}
+
+/// A path for the backup of `path` that isn't taken, so that replacing a file again keeps earlier backups:
+/// `{path}.bak`, then `{path}.bak.1`, `{path}.bak.2`, ...
+fn backup_path(path: &str) -> String {
+    let backup = format!("{path}.bak");
+    if !Path::new(&backup).exists() {
+        return backup;
+    }
+
+    (1..)
+        .map(|i| format!("{backup}.{i}"))
+        .find(|backup| !Path::new(backup).exists())
+        .unwrap()
+}
+
+/// Write an artifact according to its planned outcome, backing up replaced contents first.
+/// If writing fails, a replaced file is restored from its backup, so that no backup is left behind.
+fn apply(artifact: &Artifact) -> Result<(), io::Error> {
+    match &artifact.outcome {
+        Outcome::Kept { .. } => Ok(()),
+        Outcome::Created => fs::write(&artifact.path, &artifact.contents),
+        Outcome::Replaced { backup } => {
+            if let Some(backup) = backup {
+                if let Err(e) = fs::copy(&artifact.path, backup) {
+                    let _ = fs::remove_file(backup);
+                    return Err(e);
+                }
+            }
+
+            if let Err(e) = fs::write(&artifact.path, &artifact.contents) {
+                if let Err(e) = revert(artifact) {
+                    eprintln!("Failed to restore \"{}\": {e}", artifact.path);
+                }
+                return Err(e);
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Undo an applied artifact, restoring the previous contents of a replaced file.
+fn revert(artifact: &Artifact) -> Result<(), io::Error> {
+    match &artifact.outcome {
+        Outcome::Kept { .. } => Ok(()),
+        Outcome::Created => fs::remove_file(&artifact.path),
+        Outcome::Replaced { backup: None } => fs::write(&artifact.path, ""),
+        Outcome::Replaced {
+            backup: Some(backup),
+        } => fs::rename(backup, &artifact.path),
     }
-    file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Apply all artifacts, or none of them if writing one fails.
+fn apply_all(artifacts: &[Artifact]) -> Result<(), io::Error> {
+    for (i, artifact) in artifacts.iter().enumerate() {
+        if let Err(e) = apply(artifact) {
+            for applied in artifacts[..i].iter().rev() {
+                if let Err(e) = revert(applied) {
+                    eprintln!("Failed to restore \"{}\": {e}", applied.path);
+                }
+            }
+            return Err(io::Error::new(
+                e.kind(),
+                format!("could not write \"{}\": {e}", artifact.path),
+            ));
+        }
+    }
+    Ok(())
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+fn print_summary(artifacts: &[Artifact]) {
+    for artifact in artifacts {
+        let Artifact {
+            name, flag, path, ..
+        } = artifact;
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
+        match &artifact.outcome {
+            Outcome::Created => println!("Created {name} file \"{path}\""),
+            Outcome::Kept { is_empty: true } => println!("Kept empty {name} file \"{path}\""),
+            Outcome::Kept { is_empty: false } => {
+                println!("Kept {name} file \"{path}\". Pass `{flag}` to replace it.");
+            }
+            Outcome::Replaced { backup: None } => println!("Replaced {name} file \"{path}\""),
+            Outcome::Replaced {
+                backup: Some(backup),
+            } => println!("Replaced {name} file \"{path}\", previous contents in \"{backup}\""),
+        }
+    }
+}
+
+/// Render an expected example answer as the right-hand side of a test assertion.
+fn expected_value(answer: Option<&str>) -> String {
+    match answer {
+        None => "None".into(),
+        Some(answer) if answer.parse::<i128>().is_ok() => format!("Some({answer})"),
+        Some(answer) => format!("Some(String::from({answer:?}))"),
+    }
+}
+
+/// Replace the expected value of the assertion in the `test_part_one` and `test_part_two` tests of a module with the
+/// known example answers. Everything else in the module is left as is.
+fn update_assertions(module: &str, answers: &ExampleAnswers) -> Result<String, String> {
+    let mut module = module.to_string();
+
+    for (name, answer) in [
+        ("test_part_one", &answers.part_one),
+        ("test_part_two", &answers.part_two),
+    ] {
+        let Some(answer) = answer else {
+            continue;
+        };
+
+        let assertion = Regex::new(&format!(
+            r"(fn {name}\(\)[^{{]*\{{[^}}]*?assert_eq!\(result,\s*)([^\n]*?)(\);)"
+        ))
+        .unwrap();
+
+        if !assertion.is_match(&module) {
+            return Err(format!("no `assert_eq!(result, ...)` found in `{name}`."));
+        }
+
+        module = assertion
+            .replace(&module, |c: &Captures| {
+                format!("{}{}{}", &c[1], expected_value(Some(answer)), &c[3])
+            })
+            .into();
+    }
+
+    Ok(module)
+}
+
+/// Rewrite the assertions of the tests of an existing module with the known example answers.
+pub fn update_tests(puzzle: PuzzleId, answers: &ExampleAnswers) {
+    let module_path = puzzle.module_path();
+
+    if answers.is_empty() {
+        eprintln!(
+            "No example answers known. Pass `--part1-example` / `--part2-example` or run `cargo examples {}`.",
+            puzzle.day
+        );
+        process::exit(1);
+    }
+
+    let module = match fs::read_to_string(&module_path) {
+        Ok(module) => module,
         Err(e) => {
-            eprintln!("Failed to create module file: {e}");
+            eprintln!("Failed to read module file: {e}");
             process::exit(1);
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
+    let updated = match update_assertions(&module, answers) {
+        Ok(updated) => updated,
         Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+            eprintln!("Failed to update tests of \"{module_path}\": {e}");
             process::exit(1);
         }
-    }
+    };
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    match fs::write(&module_path, updated) {
+        Ok(()) => {
+            println!("Updated tests of \"{module_path}\":");
+            print!("{answers}");
         }
         Err(e) => {
-            eprintln!("Failed to create input file: {e}");
+            eprintln!("Failed to write module file: {e}");
             process::exit(1);
         }
     }
+}
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
+/// Create the module, input and example files of a day. Existing files are kept unless `overwrite` is set for them.
+pub fn handle(
+    puzzle: PuzzleId,
+    overwrite: Overwrite,
+    answers: &ExampleAnswers,
+    template: Option<String>,
+) {
+    let input_path = puzzle.data_path("inputs", "txt");
+    let example_path = puzzle.data_path("examples", "txt");
+    let module_path = puzzle.module_path();
+
+    let template = match read_template(template.or_else(|| env::var(TEMPLATE_ENV).ok()).as_deref())
+    {
+        Ok(template) => template,
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+            eprintln!("Failed to read template: {e}");
+            process::exit(1);
+        }
+    };
+
+    let title = fs::read_to_string(puzzle.data_path("puzzles", "md"))
+        .ok()
+        .and_then(|markdown| puzzle_title(&markdown));
+
+    for path in [&input_path, &example_path] {
+        if let Some(Err(e)) = Path::new(path).parent().map(fs::create_dir_all) {
+            eprintln!("Failed to create data directory: {e}");
             process::exit(1);
         }
     }
 
+    let artifacts = [
+        Artifact {
+            name: "module",
+            flag: "--overwrite",
+            outcome: plan(&module_path, overwrite.module),
+            path: module_path,
+            contents: render_template(&template, puzzle, title.as_deref(), answers),
+        },
+        Artifact {
+            name: "input",
+            flag: "--overwrite-input",
+            outcome: plan(&input_path, overwrite.input),
+            path: input_path,
+            contents: String::new(),
+        },
+        Artifact {
+            name: "example",
+            flag: "--overwrite-example",
+            outcome: plan(&example_path, overwrite.example),
+            path: example_path,
+            contents: String::new(),
+        },
+    ];
+
+    if let Err(e) = apply_all(&artifacts) {
+        eprintln!(
+            "Failed to scaffold day {}, no files were changed: {e}",
+            puzzle.day
+        );
+        process::exit(1);
+    }
+
+    print_summary(&artifacts);
+
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!(
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        puzzle.day, puzzle.year
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{
+        apply_all, expected_value, plan, puzzle_title, render_template, update_assertions,
+        Artifact, Outcome,
+    };
+    use crate::template::{examples::ExampleAnswers, PuzzleId};
+    use crate::{day, year};
+
+    const MODULE: &str = "pub fn part_one(input: &str) -> Option<u64> {
+    let result = input.len();
+    assert_eq!(result, 3);
+    Some(result as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file(\"examples\", PUZZLE));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file(\"examples\", PUZZLE));
+        assert_eq!(result, Some(String::from(\"abc\")));
+    }
+}
+";
+
+    #[test]
+    fn reads_puzzle_titles() {
+        assert_eq!(
+            puzzle_title("## --- Day 1: Historian Hysteria ---\n\nThe *Chief Historian*"),
+            Some("Historian Hysteria".into())
+        );
+        assert_eq!(puzzle_title("no title"), None);
+    }
+
+    #[test]
+    fn renders_templates() {
+        let puzzle = PuzzleId::new(year!(2024), day!(1));
+        let template =
+            "// %TITLE% (%YEAR%), see %URL%\nsolution!(%DAY_NUMBER%);\n%PART_ONE_EXAMPLE%";
+
+        assert_eq!(
+            render_template(template, puzzle, Some("Historian Hysteria"), &ExampleAnswers::default()),
+            "// Historian Hysteria (2024), see https://adventofcode.com/2024/day/1\nsolution!(1);\nNone"
+        );
+        assert_eq!(
+            render_template("// %TITLE%", puzzle, None, &ExampleAnswers::default()),
+            "// Day 1"
+        );
+    }
+
+    #[test]
+    fn renders_expected_values() {
+        assert_eq!(expected_value(None), "None");
+        assert_eq!(expected_value(Some("-11")), "Some(-11)");
+        assert_eq!(expected_value(Some("a,b")), "Some(String::from(\"a,b\"))");
+    }
+
+    #[test]
+    fn updates_only_test_assertions() {
+        let answers = ExampleAnswers {
+            part_one: Some("11".into()),
+            part_two: Some("31".into()),
+        };
+        let updated = update_assertions(MODULE, &answers).unwrap();
+
+        assert_eq!(
+            updated,
+            MODULE
+                .replace("assert_eq!(result, None)", "assert_eq!(result, Some(11))")
+                .replace(
+                    "assert_eq!(result, Some(String::from(\"abc\")))",
+                    "assert_eq!(result, Some(31))"
+                )
+        );
+        assert_eq!(updated.contains("assert_eq!(result, 3);"), true);
+    }
+
+    #[test]
+    fn skips_unknown_answers() {
+        let answers = ExampleAnswers {
+            part_one: None,
+            part_two: Some("31".into()),
+        };
+        let updated = update_assertions(MODULE, &answers).unwrap();
+        assert_eq!(updated.contains("assert_eq!(result, None);"), true);
+        assert_eq!(updated.contains("assert_eq!(result, Some(31));"), true);
+    }
+
+    #[test]
+    fn keeps_existing_files_unless_overwritten() {
+        let dir = env::temp_dir().join(format!("aoc-scaffold-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let path = |name: &str| dir.join(name).to_str().unwrap().to_string();
+        fs::write(path("input.txt"), "42").unwrap();
+        fs::write(path("example.txt"), "1").unwrap();
+
+        let artifact = |name: &str, overwrite: bool| Artifact {
+            name: "file",
+            flag: "--overwrite",
+            outcome: plan(&path(name), overwrite),
+            path: path(name),
+            contents: "new".into(),
+        };
+
+        let artifacts = [
+            artifact("module.rs", false),
+            artifact("input.txt", false),
+            artifact("example.txt", true),
+        ];
+
+        assert_eq!(artifacts[0].outcome, Outcome::Created);
+        assert_eq!(artifacts[1].outcome, Outcome::Kept { is_empty: false });
+        assert_eq!(
+            artifacts[2].outcome,
+            Outcome::Replaced {
+                backup: Some(path("example.txt.bak"))
+            }
+        );
+
+        apply_all(&artifacts).unwrap();
+        assert_eq!(fs::read_to_string(path("module.rs")).unwrap(), "new");
+        assert_eq!(fs::read_to_string(path("input.txt")).unwrap(), "42");
+        assert_eq!(fs::read_to_string(path("example.txt")).unwrap(), "new");
+        assert_eq!(fs::read_to_string(path("example.txt.bak")).unwrap(), "1");
+
+        // replacing the file again keeps the earlier backup.
+        let artifacts = [artifact("example.txt", true)];
+        assert_eq!(
+            artifacts[0].outcome,
+            Outcome::Replaced {
+                backup: Some(path("example.txt.bak.1"))
+            }
+        );
+
+        apply_all(&artifacts).unwrap();
+        assert_eq!(fs::read_to_string(path("example.txt.bak")).unwrap(), "1");
+        assert_eq!(
+            fs::read_to_string(path("example.txt.bak.1")).unwrap(),
+            "new"
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,79 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+
+use crate::template::stress::{complexity_class, estimate_exponent};
+use crate::template::{PuzzleId, Solution, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Run the parts of a solution against generated inputs of each of `sizes` and estimate how their run time scales
+/// with the length of the input.
+#[allow(clippy::cast_precision_loss)]
+pub fn handle(solutions: &[Solution], puzzle: PuzzleId, sizes: &[usize], seed: u64) {
+    let Some(solution) = solutions.iter().find(|s| s.puzzle == puzzle) else {
+        eprintln!("Day {} ({}) is not solved.", puzzle.day, puzzle.year);
+        process::exit(1);
+    };
+
+    let Some(generate) = solution.options.generate else {
+        eprintln!(
+            "Day {} ({}) has no input generator. Register one with `solution!({}, generate = generate)`.",
+            puzzle.day,
+            puzzle.year,
+            puzzle.day.into_inner()
+        );
+        process::exit(1);
+    };
+
+    // (part, input length, nanos) of every part that ran to completion.
+    let mut samples: Vec<(u8, usize, f64)> = vec![];
+
+    for (i, size) in sizes.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        let Ok(input) = panic::catch_unwind(|| generate(seed, *size)) else {
+            println!("Generator panicked.");
+            continue;
+        };
+
+        println!(
+            "{ANSI_BOLD}Size {size}x{ANSI_RESET} {ANSI_ITALIC}({} bytes, seed {seed}){ANSI_RESET}",
+            input.len()
+        );
+        println!("------");
+
+        for part in solution.parts {
+            match panic::catch_unwind(AssertUnwindSafe(|| (part.run)(&input, None, None))) {
+                Ok(record) if record.answer.is_some() => {
+                    samples.push((part.part, input.len(), record.stats.mean.as_nanos() as f64));
+                }
+                Ok(_) => {}
+                Err(_) => println!("Part {} panicked.", part.part),
+            }
+        }
+    }
+
+    println!();
+    println!("{ANSI_BOLD}Complexity{ANSI_RESET}");
+    println!("------");
+
+    for part in solution.parts {
+        let part_samples: Vec<(usize, f64)> = samples
+            .iter()
+            .filter(|(p, _, _)| *p == part.part)
+            .map(|(_, n, nanos)| (*n, *nanos))
+            .collect();
+
+        match estimate_exponent(&part_samples) {
+            Some(exponent) => println!(
+                "Part {}: time ∝ n^{exponent:.2}, about {}",
+                part.part,
+                complexity_class(exponent)
+            ),
+            None => println!(
+                "Part {}: not enough results at different input sizes.",
+                part.part
+            ),
+        }
+    }
+}
@@ -1,40 +1,139 @@
 use std::collections::HashSet;
+use std::process;
+use std::time::Duration;
 
-use crate::template::run_multi::run_multi;
+use crate::template::compare;
+use crate::template::run_multi::{read_answers, run_multi};
+use crate::template::runner::BenchConfig;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
-
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
-
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
+use crate::template::{
+    all_days, readme_benchmarks, Day, PartRecord, PuzzleId, Solution, Year, ANSI_BOLD, ANSI_ITALIC,
+    ANSI_RESET,
+};
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    solutions: &[Solution],
+    years: &[Year],
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    bench_config: BenchConfig,
+    compare_threshold: Option<f64>,
+    timeout: Option<Duration>,
+    jobs: usize,
+) {
+    let stored_timings: Vec<(Year, Timings)> = years
+        .iter()
+        .map(|year| match Timings::read_from_file(*year) {
+            Ok(timings) => (*year, timings),
+            // storing would replace the file, and comparing against it would compare against nothing.
+            Err(e) if store || compare_threshold.is_some() => {
+                eprintln!("Failed to read the stored timings of {year}: {e}");
+                eprintln!("Fix them before passing `--store` or `--compare`.");
+                process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("Ignoring the stored timings of {year}: {e}");
+                (*year, Timings::default())
             }
-        },
-        |day| HashSet::from([day]),
-    );
+        })
+        .collect();
+
+    let mut puzzles_to_run: HashSet<PuzzleId> = HashSet::new();
+
+    for (year, stored_timings) in &stored_timings {
+        let days_to_run: Vec<Day> = day.map_or_else(
+            || {
+                if run_all {
+                    all_days().collect()
+                } else if compare_threshold.is_some() {
+                    // when comparing, bench the days that have stored timings.
+                    stored_timings.data.iter().map(|t| t.day).collect()
+                } else {
+                    // when the `--all` flag is not set, filter out days that are fully benched.
+                    all_days()
+                        .filter(|day| !stored_timings.is_day_complete(*day))
+                        .collect()
+                }
+            },
+            |day| vec![day],
+        );
+
+        puzzles_to_run.extend(days_to_run.into_iter().map(|day| PuzzleId::new(*year, day)));
+    }
+
+    let records: Vec<PartRecord> = run_multi(
+        solutions,
+        &puzzles_to_run,
+        &read_answers(years),
+        Some(bench_config),
+        timeout,
+        jobs,
+    )
+    .into_iter()
+    .flat_map(|report| report.records)
+    .collect();
+
+    // an abandoned part keeps a core busy until the command exits.
+    if let Some(abandoned) = records.iter().find(|r| r.timed_out) {
+        eprintln!(
+            "\nWarning: day {} ({}) part {} timed out and keeps running in the background. \
+             The parts timed after it ran under load, so their timings are unreliable.",
+            abandoned.day, abandoned.year, abandoned.part
+        );
+    }
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings: Vec<(Year, Timings)> = years
+        .iter()
+        .map(|year| {
+            let year_records: Vec<PartRecord> = records
+                .iter()
+                .filter(|r| r.year == *year)
+                .cloned()
+                .collect();
+            (*year, Timings::from_records(&year_records))
+        })
+        .collect();
 
-    if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+    let total_millis: f64 = timings.iter().map(|(_, t)| t.total_millis()).sum();
+    println!("\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
+    let mut has_regressions = false;
+
+    for ((year, stored_timings), (_, timings)) in stored_timings.into_iter().zip(timings) {
+        if let Some(threshold) = compare_threshold {
+            let deltas = compare::part_deltas(&stored_timings, &timings);
+            println!();
+            compare::print_table(year, &deltas, threshold);
+            has_regressions |= deltas.iter().any(|d| d.is_regression(threshold));
+        }
+
+        if store {
+            let merged_timings = stored_timings.merge(&timings);
+            merged_timings.store_file(year).unwrap();
+
+            println!();
+
+            // the readme has a single benchmarks table, which shows the configured year.
+            if Year::from_env() != Some(year) {
+                println!("Stored updated benchmarks for {year}.");
+                continue;
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+
+            match readme_benchmarks::update(year, merged_timings) {
+                Ok(()) => {
+                    println!("Stored updated benchmarks.");
+                }
+                Err(_) => {
+                    eprintln!("Failed to store updated benchmarks.");
+                }
             }
         }
     }
+
+    if has_regressions {
+        eprintln!("Some parts regressed by more than the threshold.");
+        process::exit(1);
+    }
 }
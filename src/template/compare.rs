@@ -0,0 +1,175 @@
+/// Module that compares fresh benchmark timings against stored ones.
+use std::time::Duration;
+
+use crate::template::timings::Timings;
+use crate::template::{Day, Year, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+/// The change in execution time of a single part between two sets of timings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartDelta {
+    pub day: Day,
+    pub part: u8,
+    pub stored: Duration,
+    pub current: Duration,
+}
+
+impl PartDelta {
+    /// Signed difference in nanoseconds, positive if the part got slower.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn nanos(&self) -> f64 {
+        self.current.as_nanos() as f64 - self.stored.as_nanos() as f64
+    }
+
+    /// Signed difference as a percentage of the stored duration.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn percent(&self) -> f64 {
+        if self.stored.is_zero() {
+            return 0_f64;
+        }
+        self.nanos() / self.stored.as_nanos() as f64 * 100_f64
+    }
+
+    /// Whether the part got slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent() > threshold
+    }
+
+    /// Whether the part got faster by more than `threshold` percent.
+    pub fn is_improvement(&self, threshold: f64) -> bool {
+        self.percent() < -threshold
+    }
+}
+
+/// Pair up the parts that are present in both `stored` and `current`.
+pub fn part_deltas(stored: &Timings, current: &Timings) -> Vec<PartDelta> {
+    let mut deltas: Vec<PartDelta> = vec![];
+
+    for timing in &current.data {
+        let Some(stored_timing) = stored.data.iter().find(|t| t.day == timing.day) else {
+            continue;
+        };
+
+        for part in [1, 2] {
+            if let (Some(stored), Some(current)) = (
+                stored_timing.part_duration(part),
+                timing.part_duration(part),
+            ) {
+                deltas.push(PartDelta {
+                    day: timing.day,
+                    part,
+                    stored,
+                    current,
+                });
+            }
+        }
+    }
+
+    deltas.sort_unstable_by_key(|d| (d.day, d.part));
+    deltas
+}
+
+fn format_delta(delta: &PartDelta) -> String {
+    let sign = if delta.nanos() < 0_f64 { "-" } else { "+" };
+    let abs = delta.current.abs_diff(delta.stored);
+    format!("{sign}{abs:.1?} ({:+.2}%)", delta.percent())
+}
+
+/// Print a table of deltas, highlighting parts that changed by more than `threshold` percent.
+pub fn print_table(year: Year, deltas: &[PartDelta], threshold: f64) {
+    println!("{ANSI_BOLD}Comparison {year} (threshold: {threshold}%){ANSI_RESET}");
+    println!("------");
+
+    if deltas.is_empty() {
+        println!("No stored timings to compare against.");
+        return;
+    }
+
+    println!(
+        "{:<6} {:<6} {:>12} {:>12}   Delta",
+        "Day", "Part", "Stored", "Current"
+    );
+
+    for delta in deltas {
+        let color = if delta.is_regression(threshold) {
+            ANSI_RED
+        } else if delta.is_improvement(threshold) {
+            ANSI_GREEN
+        } else {
+            ""
+        };
+
+        println!(
+            "{:<6} {:<6} {:>12} {:>12}   {color}{}{ANSI_RESET}",
+            delta.day.to_string(),
+            delta.part,
+            format!("{:.1?}", delta.stored),
+            format!("{:.1?}", delta.current),
+            format_delta(delta),
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_delta, part_deltas, PartDelta};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+        template::{BenchStats, Day},
+    };
+
+    fn timings(day: u8, part_1: Option<u64>, part_2: Option<u64>) -> Timings {
+        let stats = |nanos| BenchStats::single(Duration::from_nanos(nanos));
+        Timings {
+            data: vec![Timing {
+                day: Day::new(day).unwrap(),
+                part_1: part_1.map(stats),
+                part_2: part_2.map(stats),
+                total_nanos: 0_f64,
+                timed_out: vec![],
+            }],
+        }
+    }
+
+    fn delta(stored: u64, current: u64) -> PartDelta {
+        PartDelta {
+            day: day!(1),
+            part: 1,
+            stored: Duration::from_nanos(stored),
+            current: Duration::from_nanos(current),
+        }
+    }
+
+    #[test]
+    fn pairs_matching_parts() {
+        let stored = timings(1, Some(100), None);
+        let current = timings(1, Some(110), Some(5_000_000));
+        let deltas = part_deltas(&stored, &current);
+        assert_eq!(deltas, vec![delta(100, 110)]);
+    }
+
+    #[test]
+    fn skips_days_without_stored_timings() {
+        let stored = timings(2, Some(100), None);
+        let current = timings(1, Some(110), None);
+        assert_eq!(part_deltas(&stored, &current).len(), 0);
+    }
+
+    #[test]
+    fn classifies_changes() {
+        assert_eq!(delta(100, 110).is_regression(5.0), true);
+        assert_eq!(delta(100, 104).is_regression(5.0), false);
+        assert_eq!(delta(100, 90).is_improvement(5.0), true);
+        assert_eq!(delta(100, 90).is_regression(5.0), false);
+    }
+
+    #[test]
+    fn formats_deltas() {
+        assert_eq!(format_delta(&delta(100, 110)), "+10.0ns (+10.00%)");
+        assert_eq!(format_delta(&delta(2_000, 1_000)), "-1.0µs (-50.00%)");
+    }
+}
@@ -0,0 +1,236 @@
+/// Extraction of example inputs and answers from puzzle descriptions saved as markdown.
+use std::{fmt::Display, fs, str::FromStr, sync::OnceLock};
+
+use regex::Regex;
+
+use crate::template::PuzzleId;
+
+static PART_TWO_HEADING: &str = "--- Part Two ---";
+
+/// A fenced code block of a puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The part of the puzzle whose description contains the block.
+    pub part: u8,
+    pub content: String,
+}
+
+/// The expected answers for the examples of a puzzle.
+///
+/// # Display
+/// This value displays as one `part_one: <answer>` / `part_two: <answer>` line per known answer,
+/// the format of the sidecar file next to the examples of a day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExampleAnswers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl ExampleAnswers {
+    /// The path of the sidecar file of a day, e.g. `data/2024/examples/01.answers`.
+    pub fn get_path(puzzle: PuzzleId) -> String {
+        puzzle.data_path("examples", "answers")
+    }
+
+    /// Read the sidecar file of a day. If not present or not valid, returns no answers.
+    pub fn read_from_file(puzzle: PuzzleId) -> Self {
+        fs::read_to_string(Self::get_path(puzzle))
+            .map_err(|x| x.to_string())
+            .and_then(|x| x.parse())
+            .unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.part_one.is_none() && self.part_two.is_none()
+    }
+
+    /// Fill the answers missing from `self` with those of `other`.
+    #[must_use]
+    pub fn or(self, other: Self) -> Self {
+        Self {
+            part_one: self.part_one.or(other.part_one),
+            part_two: self.part_two.or(other.part_two),
+        }
+    }
+}
+
+/// The code blocks and the expected example answers found in a puzzle description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PuzzleExamples {
+    pub blocks: Vec<CodeBlock>,
+    pub answers: ExampleAnswers,
+}
+
+impl PuzzleExamples {
+    /// Parse a puzzle description. Part two starts at its `--- Part Two ---` heading.
+    ///
+    /// The expected answer of a part is detected as the last emphasized code span (e.g. `` `*11*` ``)
+    /// outside of code blocks, which is how puzzle descriptions highlight the result of an example.
+    pub fn parse(markdown: &str) -> Self {
+        let (part_one, part_two) = match markdown.find(PART_TWO_HEADING) {
+            Some(i) => (&markdown[..i], Some(&markdown[i..])),
+            None => (markdown, None),
+        };
+
+        let (mut blocks, answer_one) = parse_part(part_one, 1);
+        let (blocks_two, answer_two) = part_two.map(|x| parse_part(x, 2)).unwrap_or_default();
+        blocks.extend(blocks_two);
+
+        Self {
+            blocks,
+            answers: ExampleAnswers {
+                part_one: answer_one,
+                part_two: answer_two,
+            },
+        }
+    }
+}
+
+/// Collect the code blocks of a part and its last emphasized code span.
+fn parse_part(markdown: &str, part: u8) -> (Vec<CodeBlock>, Option<String>) {
+    static EMPHASIZED_CODE: OnceLock<Regex> = OnceLock::new();
+    let emphasized_code =
+        EMPHASIZED_CODE.get_or_init(|| Regex::new(r"`\*([^`*]+)\*`|\*`([^`*]+)`\*").unwrap());
+
+    let mut blocks = vec![];
+    let mut answer = None;
+    let mut block: Option<String> = None;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match block.take() {
+                Some(content) => blocks.push(CodeBlock { part, content }),
+                None => block = Some(String::new()),
+            }
+            continue;
+        }
+
+        if let Some(content) = block.as_mut() {
+            content.push_str(line);
+            content.push('\n');
+            continue;
+        }
+
+        if let Some(captures) = emphasized_code.captures_iter(line).last() {
+            let value = captures.get(1).or_else(|| captures.get(2)).unwrap();
+            answer = Some(value.as_str().trim().to_string());
+        }
+    }
+
+    (blocks, answer)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl Display for ExampleAnswers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(answer) = &self.part_one {
+            writeln!(f, "part_one: {answer}")?;
+        }
+        if let Some(answer) = &self.part_two {
+            writeln!(f, "part_two: {answer}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ExampleAnswers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut answers = ExampleAnswers::default();
+
+        for line in s.lines().filter(|line| !line.trim().is_empty()) {
+            let (key, value) = line
+                .split_once(':')
+                .ok_or(format!("expected `<part>: <answer>`, found `{line}`."))?;
+
+            let value = Some(value.trim().to_string());
+            match key.trim() {
+                "part_one" => answers.part_one = value,
+                "part_two" => answers.part_two = value,
+                key => return Err(format!("unknown part `{key}`.")),
+            }
+        }
+
+        Ok(answers)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{CodeBlock, ExampleAnswers, PuzzleExamples};
+
+    const PUZZLE: &str = "## --- Day 1: Historian Hysteria ---
+
+For example:
+
+```
+3   4
+4   3
+```
+
+Pair up the `*smallest*` numbers. In the example above, this is `*2*` + `*1*` = `*11*`.
+
+## --- Part Two ---
+
+This time, use the same example:
+
+```
+3   4
+4   3
+```
+
+So, for these example lists, the similarity score is `*31*`.
+";
+
+    #[test]
+    fn extracts_code_blocks() {
+        let examples = PuzzleExamples::parse(PUZZLE);
+        assert_eq!(
+            examples.blocks,
+            vec![
+                CodeBlock {
+                    part: 1,
+                    content: "3   4\n4   3\n".into()
+                },
+                CodeBlock {
+                    part: 2,
+                    content: "3   4\n4   3\n".into()
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn detects_example_answers() {
+        let examples = PuzzleExamples::parse(PUZZLE);
+        assert_eq!(examples.answers.part_one, Some("11".into()));
+        assert_eq!(examples.answers.part_two, Some("31".into()));
+    }
+
+    #[test]
+    fn detects_answers_without_part_two() {
+        let (part_one, _) = PUZZLE.split_once("## --- Part Two").unwrap();
+        let examples = PuzzleExamples::parse(part_one);
+        assert_eq!(examples.blocks.len(), 1);
+        assert_eq!(examples.answers.part_one, Some("11".into()));
+        assert_eq!(examples.answers.part_two, None);
+    }
+
+    #[test]
+    fn round_trips_example_answers() {
+        let answers = ExampleAnswers {
+            part_one: Some("11".into()),
+            part_two: None,
+        };
+        assert_eq!(answers.to_string(), "part_one: 11\n");
+        assert_eq!(
+            answers.to_string().parse::<ExampleAnswers>().unwrap(),
+            answers
+        );
+        assert_eq!("part_three: 1".parse::<ExampleAnswers>().is_err(), true);
+    }
+}
@@ -0,0 +1,226 @@
+/// Log of requests made to adventofcode.com, used to space out consecutive requests.
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    str::FromStr,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, PuzzleId, Year};
+
+static FETCH_LOG_FILE_PATH: &str = "./data/fetches.json";
+
+/// Minimum time between two requests to adventofcode.com.
+pub const MIN_FETCH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The most recent request for the puzzle of a year and day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fetch {
+    pub year: Year,
+    pub day: Day,
+    /// Milliseconds since the unix epoch.
+    pub fetched_at: u64,
+}
+
+/// Represents the most recent request per year and day.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FetchLog {
+    pub data: Vec<Fetch>,
+}
+
+impl FetchLog {
+    /// Dehydrate the log to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(FETCH_LOG_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the log from a JSON file. If not present, returns an empty log.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(FETCH_LOG_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(FetchLog::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Record a request at `now`, replacing an earlier request for the same year and day.
+    pub fn record(&mut self, puzzle: PuzzleId, now: u64) {
+        self.data
+            .retain(|f| !(f.year == puzzle.year && f.day == puzzle.day));
+        self.data.push(Fetch {
+            year: puzzle.year,
+            day: puzzle.day,
+            fetched_at: now,
+        });
+    }
+
+    /// Time to wait at `now` until the next request keeps `interval` to the most recent one.
+    pub fn wait_time(&self, now: u64, interval: Duration) -> Option<Duration> {
+        let last = self.data.iter().map(|f| f.fetched_at).max()?;
+        let next = last.saturating_add(u64::try_from(interval.as_millis()).unwrap_or(u64::MAX));
+        (next > now).then(|| Duration::from_millis(next - now))
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+}
+
+/// Make a request for `puzzle`, waiting until [`MIN_FETCH_INTERVAL`] has passed since the previous request.
+pub fn throttled<T, E>(puzzle: PuzzleId, fetch: impl FnOnce() -> Result<T, E>) -> Result<T, E> {
+    let mut log = FetchLog::read_from_file();
+
+    if let Some(wait) = log.wait_time(now_millis(), MIN_FETCH_INTERVAL) {
+        println!("Waiting {wait:.1?} before the next request...");
+        thread::sleep(wait);
+    }
+
+    let result = fetch();
+
+    log.record(puzzle, now_millis());
+    if let Err(e) = log.store_file() {
+        eprintln!("Failed to write fetch log: {e}");
+    }
+
+    result
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<FetchLog> for JsonValue {
+    fn from(value: FetchLog) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for FetchLog {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(FetchLog {
+            data: json_data
+                .iter()
+                .map(Fetch::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Fetch> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Fetch) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("year".into(), JsonValue::String(value.year.to_string()));
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "fetched_at".into(),
+            JsonValue::Number(value.fetched_at as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Fetch {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected fetch to be a JSON object.")?;
+
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<String>())
+            .and_then(|year| Year::from_str(year).ok())
+            .ok_or("Expected fetch.year to be a Year struct.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected fetch.day to be a Day struct.")?;
+
+        let fetched_at = json
+            .get("fetched_at")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected fetch.fetched_at to be a number.")?;
+
+        Ok(Fetch {
+            year,
+            day,
+            fetched_at: *fetched_at as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::FetchLog;
+    use crate::{day, template::PuzzleId, year};
+
+    #[test]
+    fn records_latest_fetch_per_day() {
+        let mut log = FetchLog::default();
+        log.record(PuzzleId::new(year!(2024), day!(1)), 1_000);
+        log.record(PuzzleId::new(year!(2023), day!(1)), 2_000);
+        log.record(PuzzleId::new(year!(2024), day!(1)), 3_000);
+        assert_eq!(log.data.len(), 2);
+        assert_eq!(log.data[1].fetched_at, 3_000);
+    }
+
+    #[test]
+    fn waits_for_interval() {
+        let mut log = FetchLog::default();
+        assert_eq!(log.wait_time(10_000, Duration::from_secs(5)), None);
+
+        log.record(PuzzleId::new(year!(2024), day!(1)), 10_000);
+        log.record(PuzzleId::new(year!(2024), day!(2)), 12_000);
+        assert_eq!(
+            log.wait_time(13_000, Duration::from_secs(5)),
+            Some(Duration::from_secs(4))
+        );
+        assert_eq!(log.wait_time(17_000, Duration::from_secs(5)), None);
+    }
+
+    #[test]
+    fn round_trips_fetch_log() {
+        let mut log = FetchLog::default();
+        log.record(PuzzleId::new(year!(2024), day!(1)), 1_733_000_000_000);
+        log.record(PuzzleId::new(year!(2023), day!(2)), 1_733_000_005_000);
+        let json = tinyjson::JsonValue::from(log.clone()).stringify().unwrap();
+        assert_eq!(FetchLog::try_from(json).unwrap(), log);
+    }
+}
@@ -0,0 +1,183 @@
+/// Selection of the input a solution binary runs on.
+use std::{
+    fs,
+    io::{self, Read},
+};
+
+use crate::template::PuzzleId;
+
+/// The input a solution runs on, selected with `--input <path>`, `--example [N]` or `-` (stdin).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input of the day, `data/<year>/inputs/<day>.txt`.
+    #[default]
+    Puzzle,
+    /// The example of the day, `data/<year>/examples/<day>.txt`, or the numbered example `<day>-<n>.txt`.
+    Example(Option<u8>),
+    /// Any other file.
+    File(String),
+    Stdin,
+}
+
+impl InputSource {
+    /// Take the input arguments out of `args`, leaving all other arguments in place.
+    ///
+    /// `--example` takes an optional number, so it consumes the following argument only if that is a number.
+    pub fn take_from_args(args: &mut Vec<String>) -> Result<Self, String> {
+        let mut sources = vec![];
+
+        if let Some(index) = args.iter().position(|x| x == "--input") {
+            args.remove(index);
+            if index >= args.len() {
+                return Err("Unexpected command-line input. Format: --input <path>".into());
+            }
+            sources.push(match args.remove(index).as_str() {
+                "-" => Self::Stdin,
+                path => Self::File(path.into()),
+            });
+        }
+
+        if let Some(index) = args.iter().position(|x| x == "--example") {
+            args.remove(index);
+            let number = args.get(index).and_then(|x| x.parse::<u8>().ok());
+            if number.is_some() {
+                args.remove(index);
+            }
+            sources.push(Self::Example(number));
+        }
+
+        if let Some(index) = args.iter().position(|x| x == "-") {
+            args.remove(index);
+            sources.push(Self::Stdin);
+        }
+
+        match sources.len() {
+            0 => Ok(Self::Puzzle),
+            1 => Ok(sources.remove(0)),
+            _ => Err("Only one of `--input <path>`, `--example [N]` and `-` can be passed.".into()),
+        }
+    }
+
+    /// The arguments that select this input, to be passed on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::Example(None) => vec!["--example".into()],
+            Self::Example(Some(n)) => vec!["--example".into(), n.to_string()],
+            Self::File(path) => vec!["--input".into(), path.clone()],
+            Self::Stdin => vec!["-".into()],
+        }
+    }
+
+    /// Whether this is the puzzle input of the day, the only input that answers can be recorded and submitted for.
+    pub fn is_puzzle_input(&self) -> bool {
+        *self == Self::Puzzle
+    }
+
+    /// The file read for `puzzle`, or `None` for stdin.
+    pub fn path(&self, puzzle: PuzzleId) -> Option<String> {
+        match self {
+            Self::Puzzle => Some(puzzle.data_path("inputs", "txt")),
+            Self::Example(None) => Some(puzzle.data_path("examples", "txt")),
+            Self::Example(Some(n)) => Some(puzzle.data_part_path("examples", *n, "txt")),
+            Self::File(path) => Some(path.clone()),
+            Self::Stdin => None,
+        }
+    }
+
+    /// A description of the input for output headers, e.g. `data/2024/examples/01.txt`.
+    pub fn describe(&self, puzzle: PuzzleId) -> String {
+        self.path(puzzle).unwrap_or_else(|| "stdin".into())
+    }
+
+    pub fn read(&self, puzzle: PuzzleId) -> Result<String, io::Error> {
+        match self.path(puzzle) {
+            Some(path) => fs::read_to_string(path),
+            None => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::InputSource;
+    use crate::{day, template::PuzzleId, year};
+
+    fn take(args: &[&str]) -> (Result<InputSource, String>, Vec<String>) {
+        let mut args: Vec<String> = args.iter().map(ToString::to_string).collect();
+        let source = InputSource::take_from_args(&mut args);
+        (source, args)
+    }
+
+    #[test]
+    fn takes_input_arguments() {
+        assert_eq!(
+            take(&["solve", "1", "--release"]),
+            (
+                Ok(InputSource::Puzzle),
+                vec!["solve".into(), "1".into(), "--release".into()]
+            )
+        );
+        assert_eq!(
+            take(&["--input", "other.txt", "--time"]),
+            (
+                Ok(InputSource::File("other.txt".into())),
+                vec!["--time".into()]
+            )
+        );
+        assert_eq!(take(&["--input", "-"]).0, Ok(InputSource::Stdin));
+        assert_eq!(take(&["-"]).0, Ok(InputSource::Stdin));
+    }
+
+    #[test]
+    fn takes_optional_example_number() {
+        assert_eq!(
+            take(&["--example", "2", "--release"]),
+            (Ok(InputSource::Example(Some(2))), vec!["--release".into()])
+        );
+        assert_eq!(
+            take(&["--example", "--release"]),
+            (Ok(InputSource::Example(None)), vec!["--release".into()])
+        );
+    }
+
+    #[test]
+    fn rejects_multiple_inputs() {
+        assert_eq!(take(&["--example", "-"]).0.is_err(), true);
+        assert_eq!(take(&["--input"]).0.is_err(), true);
+    }
+
+    #[test]
+    fn round_trips_arguments() {
+        for source in [
+            InputSource::Puzzle,
+            InputSource::Example(None),
+            InputSource::Example(Some(3)),
+            InputSource::File("a.txt".into()),
+            InputSource::Stdin,
+        ] {
+            let mut args = source.to_args();
+            assert_eq!(InputSource::take_from_args(&mut args), Ok(source));
+        }
+    }
+
+    #[test]
+    fn describes_sources() {
+        let puzzle = PuzzleId::new(year!(2024), day!(1));
+        assert_eq!(
+            InputSource::Puzzle.describe(puzzle),
+            "data/2024/inputs/01.txt"
+        );
+        assert_eq!(
+            InputSource::Example(Some(2)).describe(puzzle),
+            "data/2024/examples/01-2.txt"
+        );
+        assert_eq!(InputSource::Stdin.describe(puzzle), "stdin");
+    }
+}
@@ -1,68 +1,146 @@
 use std::{env, fs};
 
 pub mod aoc_cli;
+#[cfg(feature = "native-client")]
+pub mod aoc_client;
 pub mod commands;
 pub mod runner;
+pub mod stress;
 
 pub use day::*;
+pub use examples::ExampleAnswers;
+pub use input::InputSource;
+pub use part_result::{AnswerOf, DisplayAnswer, PartError, PartResult, SourceAnswer};
+pub use puzzle_id::*;
+pub use record::*;
+pub use registry::*;
+pub use run_multi::solved_years;
+pub use stats::*;
+pub use year::*;
 
+mod answers;
+mod compare;
 mod day;
+mod examples;
+mod fetch_log;
+mod input;
+mod outcome;
+mod output;
+mod part_result;
+mod puzzle_id;
 mod readme_benchmarks;
+mod record;
+mod registry;
 mod run_multi;
+mod solution_bins;
+mod stats;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file of a puzzle to a string. E.g. like `data/2024/examples/01.txt`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.data_path(folder, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Helper function that reads a text file of a puzzle to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = cwd.join(puzzle.data_part_path(folder, part, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY`, `YEAR` and `PUZZLE` and sets up the input and runner for each part.
 ///
+/// The year is taken from the name of the solution file, which must follow the pattern `src/bin/<year>-<day>.rs`.
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Trailing `key = value` arguments set the [`SolutionOptions`]:
+///  - `generate = <fn>` registers an input generator for `cargo stress`, see [`stress::Generator`].
+///  - `timeout = <seconds>` overrides the per-part timeout of `cargo all` and `cargo time`.
+///
+/// The generated `main` runs on the puzzle input, or on the input selected with `--input <path>`, `--example [N]`
+/// or `-` for stdin, see [`InputSource`].
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr, 1 $(, $key:ident = $value:expr)* $(,)?) => {
+        $crate::solution!(@impl $day, [$($key = $value),*], [part_one, 1]);
+    };
+    ($day:expr, 2 $(, $key:ident = $value:expr)* $(,)?) => {
+        $crate::solution!(@impl $day, [$($key = $value),*], [part_two, 2]);
+    };
+    ($day:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        $crate::solution!(@impl $day, [$($key = $value),*], [part_one, 1] [part_two, 2]);
+    };
+
+    (@option generate $value:expr) => {
+        Some($value)
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    (@option timeout $value:expr) => {
+        Some(::std::time::Duration::from_secs($value))
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    (@option $key:ident $value:expr) => {
+        compile_error!(concat!("unknown solution option `", stringify!($key), "`, expected `generate` or `timeout`."))
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    // the answer of a part, with the causes of its error. Resolved here, where the type of the result is known.
+    (@answer) => {
+        |result| {
+            use $crate::template::{DisplayAnswer, SourceAnswer};
+            (&$crate::template::AnswerOf(result)).answer()
+        }
+    };
+
+    (@impl $day:expr, [$($key:ident = $value:expr),*], $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The current year.
+        const YEAR: $crate::template::Year = $crate::template::Year::from_solution_path(file!());
+
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId = $crate::template::PuzzleId::new(YEAR, DAY);
+
+        /// The solution's entry in the registry used by `cargo all` and `cargo time`.
+        pub const SOLUTION: $crate::template::Solution = $crate::template::Solution {
+            puzzle: PUZZLE,
+            parts: &[
+                $( $crate::template::SolutionPart {
+                    part: $part,
+                    run: |input, bench_config, answers| {
+                        $crate::template::runner::run_part_in_process(
+                            $func, $crate::solution!(@answer), input, PUZZLE, $part, bench_config, answers,
+                        )
+                    },
+                }, )*
+            ],
+            options: $crate::template::SolutionOptions {
+                $( $key: $crate::solution!(@option $key $value), )*
+                ..$crate::template::SolutionOptions::NONE
+            },
+        };
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let (input, source) = read_input(PUZZLE);
+            let answers = read_answers(PUZZLE, &source);
+            $( run_part($func, $crate::solution!(@answer), &input, PUZZLE, $part, &source, answers.as_ref()); )*
         }
     };
 }
@@ -0,0 +1,181 @@
+/// Module that classifies how each day went when running multiple days.
+use std::any::Any;
+
+use crate::template::{PartRecord, PuzzleId, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+/// How running a single day went.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DayOutcome {
+    /// Every part produced an answer.
+    Solved,
+    /// The day has no solution, or some of its parts did not produce an answer.
+    Unsolved,
+    /// The input file of the day is missing.
+    MissingInput,
+    /// A part returned an error, with the error chain.
+    Failed(String),
+    /// A part panicked, with the panic message.
+    Panicked(String),
+    /// A part ran into its timeout.
+    TimedOut,
+    /// The solution doesn't compile, so it was left out of the run.
+    FailedToCompile,
+}
+
+impl DayOutcome {
+    /// Classify a day whose parts all returned, from the records of its `part_count` parts.
+    pub fn from_records(part_count: usize, records: &[PartRecord]) -> Self {
+        if records.iter().any(|r| r.timed_out) {
+            Self::TimedOut
+        } else if let Some(error) = records.iter().find_map(|r| r.error.clone()) {
+            Self::Failed(error)
+        } else if part_count > 0
+            && records.len() == part_count
+            && records.iter().all(|r| r.answer.is_some())
+        {
+            Self::Solved
+        } else {
+            Self::Unsolved
+        }
+    }
+
+    /// Whether the outcome should make the command exit with a non-zero status.
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self,
+            Self::Failed(_) | Self::Panicked(_) | Self::TimedOut | Self::FailedToCompile
+        )
+    }
+
+    fn label(&self) -> String {
+        match self {
+            Self::Solved => format!("{ANSI_GREEN}solved{ANSI_RESET}"),
+            Self::Unsolved => "unsolved".into(),
+            Self::MissingInput => "input missing".into(),
+            Self::Failed(error) => format!("{ANSI_RED}failed: {error}{ANSI_RESET}"),
+            Self::Panicked(message) => format!("{ANSI_RED}panicked: {message}{ANSI_RESET}"),
+            Self::TimedOut => format!("{ANSI_RED}⏱ timed out{ANSI_RESET}"),
+            Self::FailedToCompile => format!("{ANSI_RED}failed to compile{ANSI_RESET}"),
+        }
+    }
+}
+
+/// The outcome and part records of a single day.
+#[derive(Clone, Debug)]
+pub struct DayReport {
+    pub puzzle: PuzzleId,
+    pub outcome: DayOutcome,
+    pub records: Vec<PartRecord>,
+}
+
+/// The message of a caught panic, as passed to `panic!`, `todo!` or `unwrap`.
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(ToString::to_string)
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "<no message>".into())
+}
+
+/// Print a table with the outcome of every day, followed by the number of days per outcome.
+pub fn print_summary(reports: &[DayReport]) {
+    println!("{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("------");
+
+    println!("{:<6} {:<6} {:<6} Outcome", "Year", "Day", "Parts");
+
+    for report in reports {
+        let answered = report.records.iter().filter(|r| r.answer.is_some()).count();
+
+        println!(
+            "{:<6} {:<6} {:<6} {}",
+            report.puzzle.year.to_string(),
+            report.puzzle.day.to_string(),
+            answered,
+            report.outcome.label()
+        );
+    }
+
+    let count = |f: fn(&DayOutcome) -> bool| reports.iter().filter(|r| f(&r.outcome)).count();
+
+    println!();
+    println!(
+        "{} solved, {} unsolved, {} input missing, {} failed, {} panicked, {} timed out, {} failed to compile.",
+        count(|o| *o == DayOutcome::Solved),
+        count(|o| *o == DayOutcome::Unsolved),
+        count(|o| *o == DayOutcome::MissingInput),
+        count(|o| matches!(o, DayOutcome::Failed(_))),
+        count(|o| matches!(o, DayOutcome::Panicked(_))),
+        count(|o| *o == DayOutcome::TimedOut),
+        count(|o| *o == DayOutcome::FailedToCompile),
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::panic;
+
+    use super::{panic_message, DayOutcome};
+    use crate::{
+        day,
+        template::{BenchStats, PartRecord},
+        year,
+    };
+
+    fn record(part: u8, answer: Option<&str>, timed_out: bool) -> PartRecord {
+        PartRecord {
+            year: year!(2024),
+            day: day!(1),
+            part,
+            answer: answer.map(Into::into),
+            stats: BenchStats::default(),
+            timed_out,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn classifies_records() {
+        let solved = [record(1, Some("1"), false), record(2, Some("2"), false)];
+        assert_eq!(DayOutcome::from_records(2, &solved), DayOutcome::Solved);
+
+        let partial = [record(1, Some("1"), false), record(2, None, false)];
+        assert_eq!(DayOutcome::from_records(2, &partial), DayOutcome::Unsolved);
+
+        let timed_out = [record(1, Some("1"), false), record(2, None, true)];
+        assert_eq!(
+            DayOutcome::from_records(2, &timed_out),
+            DayOutcome::TimedOut
+        );
+
+        let mut failed = record(2, None, false);
+        failed.error = Some("invalid input".into());
+        assert_eq!(
+            DayOutcome::from_records(2, &[record(1, Some("1"), false), failed]),
+            DayOutcome::Failed("invalid input".into())
+        );
+
+        assert_eq!(DayOutcome::from_records(0, &[]), DayOutcome::Unsolved);
+    }
+
+    #[test]
+    fn flags_failures() {
+        assert_eq!(DayOutcome::Failed("oops".into()).is_failure(), true);
+        assert_eq!(DayOutcome::Panicked("oops".into()).is_failure(), true);
+        assert_eq!(DayOutcome::TimedOut.is_failure(), true);
+        assert_eq!(DayOutcome::FailedToCompile.is_failure(), true);
+        assert_eq!(DayOutcome::Unsolved.is_failure(), false);
+        assert_eq!(DayOutcome::MissingInput.is_failure(), false);
+    }
+
+    #[test]
+    fn extracts_panic_messages() {
+        let static_message = panic::catch_unwind(|| panic!("static")).unwrap_err();
+        let formatted = panic::catch_unwind(|| panic!("day {}", 16)).unwrap_err();
+
+        assert_eq!(panic_message(&*static_message), "static");
+        assert_eq!(panic_message(&*formatted), "day 16");
+    }
+}
@@ -0,0 +1,86 @@
+/// Output of the runner that can be buffered per thread, so days that run concurrently can print atomically.
+use std::cell::RefCell;
+use std::fmt;
+use std::io::{stdout, Write};
+
+thread_local! {
+    static BUFFER: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Run `f`, collecting everything it prints with [`out!`] and [`outln!`] on the current thread instead of writing
+/// it to stdout.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, String) {
+    let previous = BUFFER.with(|buffer| buffer.replace(Some(String::new())));
+    let result = f();
+    let output = BUFFER.with(|buffer| buffer.replace(previous));
+    (result, output.unwrap_or_default())
+}
+
+/// Whether the output of the current thread is collected by [`capture`].
+pub fn is_capturing() -> bool {
+    BUFFER.with(|buffer| buffer.borrow().is_some())
+}
+
+/// Print to the buffer of the current thread if it is captured, to stdout otherwise.
+pub fn print(args: fmt::Arguments) {
+    let printed = BUFFER.with(|buffer| {
+        let mut buffer = buffer.borrow_mut();
+        if let Some(buffer) = buffer.as_mut() {
+            fmt::Write::write_fmt(buffer, args).unwrap();
+            true
+        } else {
+            false
+        }
+    });
+
+    if !printed {
+        let _ = stdout().write_fmt(args);
+    }
+}
+
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::template::output::print(format_args!($($arg)*))
+    };
+}
+
+macro_rules! outln {
+    () => {
+        $crate::template::output::print(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::template::output::print(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+pub(crate) use {out, outln};
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{capture, is_capturing};
+
+    #[test]
+    fn captures_output() {
+        let (result, output) = capture(|| {
+            outln!("Part {}: {}", 1, 42);
+            out!("Part 2: ");
+            outln!("{}", is_capturing());
+            7
+        });
+        assert_eq!(result, 7);
+        assert_eq!(output, "Part 1: 42\nPart 2: true\n");
+        assert_eq!(is_capturing(), false);
+    }
+
+    #[test]
+    fn nests_captures() {
+        let (_, outer) = capture(|| {
+            out!("a");
+            let (_, inner) = capture(|| out!("b"));
+            out!("{inner}");
+        });
+        assert_eq!(outer, "ab");
+    }
+}
@@ -0,0 +1,179 @@
+/// The values a solution part can return.
+use std::any::Any;
+use std::error::Error;
+use std::fmt::Display;
+
+/// Why a solution part did not produce an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PartError {
+    /// The part returned `None`, which the scaffolded solutions do until they are implemented.
+    NotImplemented,
+    /// The part returned an error: the error, followed by its causes.
+    Failed(Vec<String>),
+}
+
+impl PartError {
+    /// A single line description, e.g. `failed: invalid input: invalid digit found in string`.
+    pub fn describe(&self) -> String {
+        match self {
+            Self::NotImplemented => "not implemented".into(),
+            Self::Failed(chain) => format!("failed: {}", chain.join(": ")),
+        }
+    }
+}
+
+/// The return value of a solution part: either an `Option<T>`, or a `Result<T, E>` with an error that can be
+/// displayed, e.g. a `String`, a `Box<dyn Error>` or an `anyhow::Error`.
+pub trait PartResult {
+    type Answer: Display;
+
+    fn answer(&self) -> Result<&Self::Answer, PartError>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
+
+    fn answer(&self) -> Result<&T, PartError> {
+        self.as_ref().ok_or(PartError::NotImplemented)
+    }
+}
+
+impl<T: Display, E: Display + 'static> PartResult for Result<T, E> {
+    type Answer = T;
+
+    fn answer(&self) -> Result<&T, PartError> {
+        self.as_ref().map_err(|e| PartError::Failed(error_chain(e)))
+    }
+}
+
+/// The answer of the value returned by a solution part, picked by the [`solution!`](crate::solution) macro while its
+/// type is known. `(&AnswerOf(&result)).answer()` resolves to [`SourceAnswer`] for a `Result` whose error implements
+/// [`Error`], which walks its causes, and falls back to [`PartResult::answer`] through [`DisplayAnswer`] otherwise.
+pub struct AnswerOf<'a, R>(pub &'a R);
+
+pub trait SourceAnswer<'a> {
+    type Answer;
+
+    fn answer(&self) -> Result<&'a Self::Answer, PartError>;
+}
+
+impl<'a, T: Display, E: Error + 'static> SourceAnswer<'a> for AnswerOf<'a, Result<T, E>> {
+    type Answer = T;
+
+    fn answer(&self) -> Result<&'a T, PartError> {
+        self.0
+            .as_ref()
+            .map_err(|e| PartError::Failed(source_chain(e)))
+    }
+}
+
+pub trait DisplayAnswer<'a> {
+    type Answer;
+
+    fn answer(&self) -> Result<&'a Self::Answer, PartError>;
+}
+
+impl<'a, R: PartResult> DisplayAnswer<'a> for &AnswerOf<'a, R> {
+    type Answer = R::Answer;
+
+    fn answer(&self) -> Result<&'a R::Answer, PartError> {
+        self.0.answer()
+    }
+}
+
+/// The error followed by its causes, walked with [`Error::source`].
+fn source_chain(mut error: &dyn Error) -> Vec<String> {
+    let mut chain = vec![error.to_string()];
+    while let Some(source) = error.source() {
+        chain.push(source.to_string());
+        error = source;
+    }
+    chain
+}
+
+/// The error followed by its causes. The causes of boxed errors are walked with [`Error::source`], other errors
+/// are formatted with `{:#}`, which prints the whole chain of an `anyhow::Error`. The causes of other errors that
+/// implement [`Error`] are walked by [`AnswerOf`].
+fn error_chain<E: Display + 'static>(error: &E) -> Vec<String> {
+    let any = error as &dyn Any;
+
+    let boxed: Option<&dyn Error> = any
+        .downcast_ref::<Box<dyn Error>>()
+        .map(|e| e.as_ref() as &dyn Error)
+        .or_else(|| {
+            any.downcast_ref::<Box<dyn Error + Send + Sync>>()
+                .map(|e| e.as_ref() as &dyn Error)
+        });
+
+    boxed.map_or_else(|| vec![format!("{error:#}")], source_chain)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::error::Error;
+    use std::fmt;
+
+    use super::{AnswerOf, DisplayAnswer, PartError, PartResult, SourceAnswer};
+
+    #[derive(Debug)]
+    struct ParseError(std::num::ParseIntError);
+
+    impl fmt::Display for ParseError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "invalid input")
+        }
+    }
+
+    impl Error for ParseError {
+        fn source(&self) -> Option<&(dyn Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn handles_options() {
+        assert_eq!(Some(42).answer(), Ok(&42));
+        assert_eq!(None::<u64>.answer(), Err(PartError::NotImplemented));
+    }
+
+    #[test]
+    fn handles_results() {
+        assert_eq!(Ok::<u64, String>(42).answer(), Ok(&42));
+        assert_eq!(
+            Err::<u64, String>("no path".into()).answer(),
+            Err(PartError::Failed(vec!["no path".into()]))
+        );
+    }
+
+    #[test]
+    fn walks_error_chains() {
+        let cause = "x".parse::<u64>().unwrap_err();
+        let result: Result<u64, Box<dyn Error>> = Err(Box::new(ParseError(cause)));
+        let error = result.answer().unwrap_err();
+        assert_eq!(
+            error.describe(),
+            "failed: invalid input: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn walks_error_chains_of_concrete_errors() {
+        let cause = "x".parse::<u64>().unwrap_err();
+        let result: Result<u64, ParseError> = Err(ParseError(cause));
+        let error = (&AnswerOf(&result)).answer().unwrap_err();
+        assert_eq!(
+            error.describe(),
+            "failed: invalid input: invalid digit found in string"
+        );
+
+        // errors that don't implement `Error` fall back to `PartResult::answer`.
+        let result: Result<u64, String> = Err("no path".into());
+        assert_eq!(
+            (&AnswerOf(&result)).answer(),
+            Err(PartError::Failed(vec!["no path".into()]))
+        );
+        assert_eq!((&AnswerOf(&Some(42))).answer(), Ok(&42));
+    }
+}
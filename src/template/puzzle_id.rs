@@ -0,0 +1,81 @@
+use std::error::Error;
+use std::fmt::Display;
+
+use crate::template::{Day, Year};
+
+/// A puzzle of advent of code, identified by its year and day.
+///
+/// # Display
+/// This value displays as `<year>/<day>`, e.g. `2024/01`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// The name of the solution binary, e.g. `2024-01`.
+    pub fn bin_name(self) -> String {
+        format!("{}-{}", self.year, self.day)
+    }
+
+    /// The path of the solution module, e.g. `src/bin/2024-01.rs`.
+    pub fn module_path(self) -> String {
+        format!("src/bin/{}.rs", self.bin_name())
+    }
+
+    /// The path of a data file of the puzzle, e.g. `data/2024/inputs/01.txt`.
+    pub fn data_path(self, folder: &str, extension: &str) -> String {
+        format!("data/{}/{folder}/{}.{extension}", self.year, self.day)
+    }
+
+    /// The path of a numbered data file of the puzzle, e.g. `data/2024/examples/01-2.txt`.
+    pub fn data_part_path(self, folder: &str, part: u8, extension: &str) -> String {
+        format!(
+            "data/{}/{folder}/{}-{part}.{extension}",
+            self.year, self.day
+        )
+    }
+}
+
+/// Parse the puzzle of a single-puzzle command. The year is read from `--year`, then from `AOC_YEAR`.
+pub fn parse_puzzle(args: &mut pico_args::Arguments) -> Result<PuzzleId, Box<dyn Error>> {
+    let year = args.opt_value_from_str::<_, Year>("--year")?;
+    let day = args.free_from_str()?;
+
+    match year.or_else(Year::from_env) {
+        Some(year) => Ok(PuzzleId::new(year, day)),
+        None => Err("no year specified. Pass `--year` or set `AOC_YEAR`.".into()),
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleId;
+    use crate::{day, year};
+
+    #[test]
+    fn builds_paths() {
+        let puzzle = PuzzleId::new(year!(2024), day!(7));
+        assert_eq!(puzzle.to_string(), "2024/07");
+        assert_eq!(puzzle.bin_name(), "2024-07");
+        assert_eq!(puzzle.module_path(), "src/bin/2024-07.rs");
+        assert_eq!(puzzle.data_path("inputs", "txt"), "data/2024/inputs/07.txt");
+        assert_eq!(
+            puzzle.data_part_path("examples", 2, "txt"),
+            "data/2024/examples/07-2.txt"
+        );
+    }
+}
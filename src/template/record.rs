@@ -0,0 +1,204 @@
+/// Machine-readable results of running a solution part.
+use std::{
+    collections::HashMap,
+    env,
+    fs::OpenOptions,
+    io::{self, Write},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{BenchStats, Day, Year};
+
+/// Environment variable that, when set to a file path, makes the runner append a JSON line per part to that file.
+pub const RECORDS_FILE_ENV: &str = "AOC_RECORDS_FILE";
+
+/// The result and timing of a single solution part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartRecord {
+    pub year: Year,
+    pub day: Day,
+    pub part: u8,
+    /// The answer returned by the part, if any.
+    pub answer: Option<String>,
+    pub stats: BenchStats,
+    /// Whether the part was abandoned after running into its timeout.
+    pub timed_out: bool,
+    /// The error chain of a part that returned an error.
+    pub error: Option<String>,
+}
+
+impl PartRecord {
+    /// Append the record as a single JSON line to the file named by [`RECORDS_FILE_ENV`], if set.
+    pub fn emit(&self) -> Result<(), io::Error> {
+        let Ok(path) = env::var(RECORDS_FILE_ENV) else {
+            return Ok(());
+        };
+
+        let json = JsonValue::from(self)
+            .stringify()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{json}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartRecord> for JsonValue {
+    fn from(value: &PartRecord) -> Self {
+        let mut map = HashMap::<String, JsonValue>::from(&value.stats);
+
+        map.insert("year".into(), JsonValue::String(value.year.to_string()));
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("timed_out".into(), JsonValue::Boolean(value.timed_out));
+        map.insert(
+            "error".into(),
+            match &value.error {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartRecord {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected record to be a JSON object.")?;
+
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<String>())
+            .and_then(|year| Year::from_str(year).ok())
+            .ok_or("Expected record.year to be a Year struct.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected record.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .ok_or("Expected record.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected record.answer to be null or string.")?;
+
+        // records written before timeouts were introduced have no `timed_out` key.
+        let timed_out = match json.get("timed_out") {
+            None => false,
+            Some(v) => *v
+                .get::<bool>()
+                .ok_or("Expected record.timed_out to be a boolean.")?,
+        };
+
+        // records written before parts could return errors have no `error` key.
+        let error = match json.get("error") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .cloned()
+                    .ok_or("Expected record.error to be null or string.")?,
+            ),
+        };
+
+        Ok(PartRecord {
+            year,
+            day,
+            part,
+            answer: answer.cloned(),
+            stats: BenchStats::try_from(json)?,
+            timed_out,
+            error,
+        })
+    }
+}
+
+impl FromStr for PartRecord {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not a valid JSON line."))?;
+        PartRecord::try_from(&json)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::PartRecord;
+    use crate::{day, template::BenchStats, year};
+
+    fn get_mock_record() -> PartRecord {
+        PartRecord {
+            year: year!(2024),
+            day: day!(3),
+            part: 2,
+            answer: Some("(1 @ 2) samples)".into()),
+            stats: BenchStats::from_samples(&[
+                Duration::from_nanos(70_000),
+                Duration::from_nanos(74_130),
+                Duration::from_nanos(80_000),
+            ]),
+            timed_out: false,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn round_trips_records() {
+        let record = get_mock_record();
+        let line = JsonValue::from(&record).stringify().unwrap();
+        assert_eq!(line.parse::<PartRecord>().unwrap(), record);
+
+        let failed = PartRecord {
+            answer: None,
+            error: Some("failed: invalid input".into()),
+            ..get_mock_record()
+        };
+        let line = JsonValue::from(&failed).stringify().unwrap();
+        assert_eq!(line.parse::<PartRecord>().unwrap(), failed);
+    }
+
+    #[test]
+    fn handles_missing_answers() {
+        let line = r#"{ "year": "2024", "day": "01", "part": 1, "answer": null, "nanos": 10, "median_nanos": 10, "min_nanos": 10, "max_nanos": 10, "p95_nanos": 10, "stddev_nanos": 0, "samples": 1, "outliers": 0 }"#;
+        let record = line.parse::<PartRecord>().unwrap();
+        assert_eq!(record.answer, None);
+        assert_eq!(record.error, None);
+        assert_eq!(record.stats, BenchStats::single(Duration::from_nanos(10)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_records() {
+        r#"{ "day": "01", "part": 1 }"#.parse::<PartRecord>().unwrap();
+    }
+}
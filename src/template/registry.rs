@@ -0,0 +1,43 @@
+/// Types that allow solutions to be run in-process by the `all` and `time` commands.
+use std::time::Duration;
+
+use crate::template::answers::Answers;
+use crate::template::runner::BenchConfig;
+use crate::template::stress::Generator;
+use crate::template::{PartRecord, PuzzleId};
+
+/// A single part of a solution, wrapped by the [`solution!`](crate::solution) macro.
+#[derive(Clone, Copy)]
+pub struct SolutionPart {
+    pub part: u8,
+    /// Runs the part against an input, benching it if a config is passed, and prints its result.
+    /// The result is verified against the recorded answers if they are passed, i.e. for the puzzle input.
+    pub run: fn(&str, Option<BenchConfig>, Option<&Answers>) -> PartRecord,
+}
+
+/// A solution registered by the [`solution!`](crate::solution) macro.
+///
+/// The `registry` binary collects the `SOLUTION` constant of every `src/bin/<year>-<day>.rs` into a table
+/// at build time, so all days can run in a single process.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    pub parts: &'static [SolutionPart],
+    pub options: SolutionOptions,
+}
+
+/// The optional `key = value` arguments of the [`solution!`](crate::solution) macro.
+#[derive(Clone, Copy, Debug)]
+pub struct SolutionOptions {
+    /// Generates inputs of a given size for `cargo stress`, if the solution registered a generator.
+    pub generate: Option<Generator>,
+    /// Overrides the `--timeout` of `cargo all` and `cargo time` for each part of this solution.
+    pub timeout: Option<Duration>,
+}
+
+impl SolutionOptions {
+    pub const NONE: Self = Self {
+        generate: None,
+        timeout: None,
+    };
+}
@@ -1,257 +1,290 @@
-use std::{collections::HashSet, io};
-
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
-
-use super::{
-    all_days,
-    timings::{Timing, Timings},
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+use crate::template::{
+    BenchStats, PartRecord, PuzzleId, Solution, SolutionPart, Year, ANSI_BOLD, ANSI_RESET,
+    RECORDS_FILE_ENV,
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+use super::answers::Answers;
+use super::outcome::{panic_message, DayOutcome, DayReport};
+use super::output::{self, out, outln};
+use super::runner::{self, BenchConfig};
+
+/// Parts run on their own thread when they have a timeout, and days on a thread per job. Use the stack size of the
+/// main thread, so that solutions don't overflow the stack in `cargo all` when they don't in `cargo solve`.
+const PART_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Run the solutions for `puzzles_to_run` in-process, in year and day order, benching them if a config is passed.
+/// Puzzles that are not part of `solutions` are reported as not solved.
+///
+/// If a timeout is passed (or the solution sets one), a part that runs into it is reported as timed out and the
+/// runner moves on to the next day.
+///
+/// With more than one job, days run concurrently on that many threads. The output of each day is buffered and
+/// printed at once, still in year and day order.
+/// Results are verified against the recorded `answers` of their year, see [`read_answers`].
+/// Returns a report of every day, with the records of all parts that ran to completion or timed out.
+pub fn run_multi(
+    solutions: &[Solution],
+    puzzles_to_run: &HashSet<PuzzleId>,
+    answers: &HashMap<Year, Arc<Answers>>,
+    bench_config: Option<BenchConfig>,
+    timeout: Option<Duration>,
+    jobs: usize,
+) -> Vec<DayReport> {
+    // NOTE: use non-duplicate, sorted puzzle values.
+    let mut puzzles: Vec<PuzzleId> = puzzles_to_run.iter().copied().collect();
+    puzzles.sort_unstable();
+
+    let run = |puzzle: PuzzleId| {
+        let answers = answers.get(&puzzle.year);
+        run_day(solutions, puzzle, answers, bench_config, timeout)
+    };
 
-    let mut need_space = false;
+    if jobs <= 1 {
+        let mut reports: Vec<DayReport> = vec![];
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
+        for (i, puzzle) in puzzles.into_iter().enumerate() {
+            if i > 0 {
                 println!();
             }
-            need_space = true;
-
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
-
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            reports.push(run(puzzle));
+        }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
-
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
-        Some(timings)
-    } else {
-        None
+        return reports;
     }
-}
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
+    run_concurrently(&puzzles, jobs, run)
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
+/// The recorded answers of `years`, read once per run. A year whose answers file is malformed is reported and left
+/// out, so its results are not verified.
+pub fn read_answers(years: &[Year]) -> HashMap<Year, Arc<Answers>> {
+    years
+        .iter()
+        .filter_map(|year| match Answers::read_from_file(*year) {
+            Ok(answers) => Some((*year, Arc::new(answers))),
+            Err(e) => {
+                eprintln!("Not verifying answers of {year}: {e}");
+                None
+            }
+        })
+        .collect()
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
+/// Run each of `puzzles` on one of `jobs` threads, printing the buffered output of a day as soon as it and all
+/// days before it are done.
+fn run_concurrently(
+    puzzles: &[PuzzleId],
+    jobs: usize,
+    run: impl Fn(PuzzleId) -> DayReport + Sync,
+) -> Vec<DayReport> {
+    let mut reports: Vec<DayReport> = vec![];
+    let next_index = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(puzzles.len()) {
+            let sender = sender.clone();
+            let (next_index, run) = (&next_index, &run);
+
+            thread::Builder::new()
+                .stack_size(PART_STACK_SIZE)
+                .spawn_scoped(scope, move || {
+                    while let Some(puzzle) = puzzles.get(next_index.fetch_add(1, Ordering::Relaxed))
+                    {
+                        let (report, output) = output::capture(|| run(*puzzle));
+                        if sender.send((report, output)).is_err() {
+                            break;
+                        }
+                    }
+                })
+                .expect("could not spawn thread for running days");
+        }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
-    };
+        drop(sender);
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+        let mut done: BTreeMap<PuzzleId, (DayReport, String)> = BTreeMap::new();
+        let mut pending = puzzles.iter().peekable();
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        for (report, output) in receiver {
+            done.insert(report.puzzle, (report, output));
 
-        if is_release {
-            args.push("--release");
+            while let Some((report, output)) =
+                pending.peek().and_then(|puzzle| done.remove(*puzzle))
+            {
+                if pending.next() != puzzles.first() {
+                    println!();
+                }
+                print!("{output}");
+                reports.push(report);
+            }
         }
+    });
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
+    reports
+}
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+/// Run all parts of a single day, reading its input from the inputs folder.
+fn run_day(
+    solutions: &[Solution],
+    puzzle: PuzzleId,
+    answers: Option<&Arc<Answers>>,
+    bench_config: Option<BenchConfig>,
+    timeout: Option<Duration>,
+) -> DayReport {
+    let mut records: Vec<PartRecord> = vec![];
+    let report = |outcome, records| DayReport {
+        puzzle,
+        outcome,
+        records,
+    };
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+    outln!(
+        "{ANSI_BOLD}Day {} ({}){ANSI_RESET}",
+        puzzle.day,
+        puzzle.year
+    );
+    outln!("------");
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+    let Some(solution) = solutions.iter().find(|s| s.puzzle == puzzle) else {
+        outln!("Not solved.");
+        return report(DayOutcome::Unsolved, records);
+    };
 
-        let mut output = vec![];
+    let Ok(input) = fs::read_to_string(get_path_for_input(puzzle)) else {
+        outln!("Input file missing.");
+        return report(DayOutcome::MissingInput, records);
+    };
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+    let input: Arc<str> = input.into();
+    let timeout = solution.options.timeout.or(timeout);
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+    for part in solution.parts {
+        match run_part(puzzle, *part, &input, answers, bench_config, timeout) {
+            PartOutcome::Finished(record) => records.push(record),
+            // a panicking solution should not abort the remaining days.
+            PartOutcome::Panicked(message) => {
+                outln!("Part {}: panicked: {message}", part.part);
+                return report(DayOutcome::Panicked(message), records);
+            }
+            PartOutcome::TimedOut(record) => {
+                runner::return_to_line_start();
+                outln!(
+                    "Part {}: ⏱ timed out ({:.1?})",
+                    part.part,
+                    record.stats.mean
+                );
+                if let Err(e) = record.emit() {
+                    eprintln!("Failed to write record to ${RECORDS_FILE_ENV}: {e}");
+                }
+                records.push(record);
+                return report(DayOutcome::TimedOut, records);
+            }
         }
-
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+    report(
+        DayOutcome::from_records(solution.parts.len(), &records),
+        records,
+    )
+}
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+enum PartOutcome {
+    Finished(PartRecord),
+    Panicked(String),
+    TimedOut(PartRecord),
+}
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+/// Run a part, on a separate thread that is abandoned once the part runs into its timeout if one is passed.
+/// An abandoned part keeps running, and slows down the parts that run after it.
+fn run_part(
+    puzzle: PuzzleId,
+    part: SolutionPart,
+    input: &Arc<str>,
+    answers: Option<&Arc<Answers>>,
+    bench_config: Option<BenchConfig>,
+    timeout: Option<Duration>,
+) -> PartOutcome {
+    let Some(timeout) = timeout else {
+        let run = || (part.run)(input, bench_config, answers.map(AsRef::as_ref));
+        return match panic::catch_unwind(AssertUnwindSafe(run)) {
+            Ok(record) => PartOutcome::Finished(record),
+            Err(payload) => PartOutcome::Panicked(panic_message(&*payload)),
+        };
+    };
 
-                timings.total_nanos += nanos;
+    let deadline = get_deadline(timeout, bench_config);
+    let (sender, receiver) = mpsc::channel();
+    let thread_input = Arc::clone(input);
+    let thread_answers = answers.cloned();
+    // a day that runs concurrently buffers its output, which has to include the output of the part's thread.
+    let is_capturing = output::is_capturing();
+
+    let handle = thread::Builder::new()
+        .stack_size(PART_STACK_SIZE)
+        .spawn(move || {
+            let run = || (part.run)(&thread_input, bench_config, thread_answers.as_deref());
+            let _ = sender.send(if is_capturing {
+                output::capture(run)
+            } else {
+                (run(), String::new())
             });
+        })
+        .expect("could not spawn thread for solution part");
 
-        timings
-    }
-
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    match receiver.recv_timeout(deadline) {
+        Ok((record, part_output)) => {
+            out!("{part_output}");
+            PartOutcome::Finished(record)
+        }
+        // the sender is dropped without sending if the part panicked.
+        Err(mpsc::RecvTimeoutError::Disconnected) => PartOutcome::Panicked(
+            handle
+                .join()
+                .err()
+                .map_or_else(|| "<no message>".into(), |payload| panic_message(&*payload)),
+        ),
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            runner::abandon(handle.thread().id());
+            PartOutcome::TimedOut(PartRecord {
+                year: puzzle.year,
+                day: puzzle.day,
+                part: part.part,
+                answer: None,
+                // the deadline is extended when benching, but the part is reported against its timeout.
+                stats: BenchStats::single(timeout),
+                timed_out: true,
+                error: None,
+            })
+        }
     }
+}
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+/// The time a part may take before it is abandoned. When benching, a part that finishes its first run within the
+/// timeout still has to run through the warmup and at least 10 samples, so the deadline is extended accordingly.
+fn get_deadline(timeout: Duration, bench_config: Option<BenchConfig>) -> Duration {
+    match bench_config {
+        Some(config) => timeout * 12 + config.warmup + config.budget,
+        None => timeout,
     }
+}
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+/// The years that have at least one solution, in order.
+pub fn solved_years(solutions: &[Solution]) -> Vec<Year> {
+    let mut years: Vec<Year> = solutions.iter().map(|s| s.puzzle.year).collect();
+    years.sort_unstable();
+    years.dedup();
+    years
+}
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
-    }
+#[must_use]
+pub fn get_path_for_input(puzzle: PuzzleId) -> String {
+    format!("./{}", puzzle.data_path("inputs", "txt"))
 }
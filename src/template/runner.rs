@@ -2,34 +2,247 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::sync::Mutex;
+use std::thread::{self, ThreadId};
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::answers::{Answers, Verdict};
+use crate::template::output::{self, out, outln};
+use crate::template::submissions::{Outcome, Submissions};
+use crate::template::{
+    aoc_cli, BenchStats, InputSource, PartError, PartRecord, PartResult, PuzzleId, ANSI_ITALIC,
+    ANSI_RESET, RECORDS_FILE_ENV,
+};
+use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_RED};
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Settings for benching a solution part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchConfig {
+    /// Approximate wall time spent on measured samples.
+    pub budget: Duration,
+    /// Wall time spent running the part before samples are measured.
+    pub warmup: Duration,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            budget: Duration::from_secs(1),
+            warmup: Duration::from_millis(100),
+        }
+    }
+}
+
+impl BenchConfig {
+    /// Build a bench config from the `--time`, `--budget <ms>` and `--warmup <ms>` arguments of a solution binary.
+    /// Returns `None` if the part should not be benched.
+    fn from_args() -> Option<Self> {
+        let args: Vec<String> = env::args().collect();
+
+        if !args.contains(&"--time".into()) {
+            return None;
+        }
+
+        let millis_arg = |name: &str| {
+            let index = args.iter().position(|x| x == name)?;
+            let Some(Ok(millis)) = args.get(index + 1).map(|x| x.parse::<u64>()) else {
+                eprintln!("Unexpected command-line input. Format: {name} <milliseconds>");
+                process::exit(1);
+            };
+            Some(Duration::from_millis(millis))
+        };
+
+        let default = Self::default();
+        Some(Self {
+            budget: millis_arg("--budget").unwrap_or(default.budget),
+            warmup: millis_arg("--warmup").unwrap_or(default.warmup),
+        })
+    }
+}
+
+/// Threads whose part ran into its timeout. A running function can't be cancelled, so these threads keep running
+/// until the process exits, but their output is suppressed.
+static ABANDONED: Mutex<Vec<ThreadId>> = Mutex::new(Vec::new());
+
+/// Mark the thread running a part as abandoned after it ran into its timeout.
+pub(crate) fn abandon(id: ThreadId) {
+    ABANDONED.lock().unwrap().push(id);
+}
+
+fn is_abandoned() -> bool {
+    let id = thread::current().id();
+    ABANDONED.lock().is_ok_and(|ids| ids.contains(&id))
+}
+
+/// Read the input selected by the arguments of a solution binary (see [`InputSource`]) and print the output header.
+pub fn read_input(puzzle: PuzzleId) -> (String, InputSource) {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+
+    let source = match InputSource::take_from_args(&mut args) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("{e}");
+            process::exit(1);
+        }
+    };
+
+    let description = source.describe(puzzle);
+
+    let input = match source.read(puzzle) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Could not read input from {description}: {e}");
+            process::exit(1);
+        }
+    };
+
+    println!(
+        "{ANSI_BOLD}Day {} ({}){ANSI_RESET} {ANSI_ITALIC}{description}{ANSI_RESET}",
+        puzzle.day, puzzle.year
+    );
+    println!("------");
+
+    (input, source)
+}
+
+/// Read the recorded answers that the results of a solution binary are verified against, if it runs on the puzzle
+/// input. A malformed answers file is reported, and the results are not verified.
+pub fn read_answers(puzzle: PuzzleId, source: &InputSource) -> Option<Answers> {
+    if !source.is_puzzle_input() {
+        return None;
+    }
+
+    Answers::read_from_file(puzzle.year)
+        .inspect_err(|e| eprintln!("Not verifying answers: {e}"))
+        .ok()
+}
+
+pub fn run_part<I: Copy, R: PartResult>(
+    func: impl Fn(I) -> R,
+    answer_of: impl Fn(&R) -> Result<&R::Answer, PartError>,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    source: &InputSource,
+    answers: Option<&Answers>,
+) {
+    let bench_config = BenchConfig::from_args();
+    let is_puzzle_input = source.is_puzzle_input();
+    let record = run_and_record(func, answer_of, input, puzzle, part, bench_config, answers);
+
+    let Some(result) = record.answer else {
+        return;
+    };
+
+    if is_puzzle_input {
+        record_answer(&result, puzzle, part);
+        submit_result(&result, puzzle, part);
+    } else if ["--record", "--submit"]
+        .iter()
+        .any(|flag| env::args().any(|x| x == *flag))
+    {
+        eprintln!(
+            "Not recording or submitting part {part}, as {} is not the puzzle input.",
+            source.describe(puzzle)
+        );
+    }
+}
+
+/// Run a solution part from within the `registry` binary, returning its [`PartRecord`].
+pub fn run_part_in_process<I: Copy, R: PartResult>(
+    func: impl Fn(I) -> R,
+    answer_of: impl Fn(&R) -> Result<&R::Answer, PartError>,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    bench_config: Option<BenchConfig>,
+    answers: Option<&Answers>,
+) -> PartRecord {
+    run_and_record(func, answer_of, input, puzzle, part, bench_config, answers)
+}
+
+/// Run a solution part, print its result and emit a [`PartRecord`] to the records file (if configured).
+/// The result is verified against the recorded `answers` only if they are passed, i.e. for the puzzle input.
+fn run_and_record<I: Copy, R: PartResult>(
+    func: impl Fn(I) -> R,
+    answer_of: impl Fn(&R) -> Result<&R::Answer, PartError>,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    bench_config: Option<BenchConfig>,
+    answers: Option<&Answers>,
+) -> PartRecord {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, stats) = run_timed(func, input, bench_config, |result| {
+        // intermediate results are only useful while a part is running in front of the user.
+        if !is_abandoned() && !output::is_capturing() {
+            print_result(&answer_of(result), &part_str, "");
+        }
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let outcome = answer_of(&result);
+    let answer = outcome.as_ref().ok().map(ToString::to_string);
+    let error = match &outcome {
+        Err(PartError::Failed(chain)) => Some(chain.join(": ")),
+        _ => None,
+    };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    // the runner already reported the timeout and moved on.
+    if is_abandoned() {
+        let record = PartRecord {
+            year: puzzle.year,
+            day: puzzle.day,
+            part,
+            answer: None,
+            stats,
+            timed_out: true,
+            error: None,
+        };
+        return record;
     }
+
+    let verdict = answers.map_or(Verdict::Unknown, |answers| {
+        answers.verify(puzzle.day, part, answer.as_deref())
+    });
+
+    print_result(
+        &outcome,
+        &part_str,
+        &format!("{}{}", format_duration(&stats), format_verdict(&verdict)),
+    );
+
+    if stats.samples > 1 {
+        print_stats(&stats);
+    }
+
+    let record = PartRecord {
+        year: puzzle.year,
+        day: puzzle.day,
+        part,
+        answer,
+        stats,
+        timed_out: false,
+        error,
+    };
+
+    if let Err(e) = record.emit() {
+        eprintln!("Failed to write record to ${RECORDS_FILE_ENV}: {e}");
+    }
+
+    record
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Run a solution part. The behavior differs depending on whether a bench config is passed:
+///  1. without, the function is executed once.
+///  2. with, the function is warmed up and then benched (approx. the time budget or 10 samples, whatever take longer.)
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    bench_config: Option<BenchConfig>,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, BenchStats) {
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -41,23 +254,32 @@ fn run_timed<I: Copy, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+    let stats = match bench_config.filter(|_| !is_abandoned()) {
+        Some(config) => bench(func, input, &config),
+        None => BenchStats::single(base_time),
     };
 
-    (result, run.0, run.1)
+    (result, stats)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, config: &BenchConfig) -> BenchStats {
     let mut stdout = stdout();
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    if !output::is_capturing() {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout.flush();
+    }
+
+    // warm up caches and branch predictors, using the runs to estimate the cost of an iteration.
+    let warmup_timer = Instant::now();
+    let mut warmup_iterations: u128 = 0;
+    while warmup_iterations == 0 || warmup_timer.elapsed() < config.warmup {
+        black_box(func(black_box(input)));
+        warmup_iterations += 1;
+    }
+    let estimate = warmup_timer.elapsed().as_nanos() / warmup_iterations;
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench_iterations = (config.budget.as_nanos() / cmp::max(estimate, 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -67,72 +289,135 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_samples(&timers)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
-}
+fn format_duration(stats: &BenchStats) -> String {
+    let BenchStats {
+        mean,
+        stddev,
+        samples,
+        ..
+    } = stats;
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+    if *samples == 1 {
+        format!(" ({mean:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({mean:.1?} ± {stddev:.1?} @ {samples} samples)")
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn format_verdict(verdict: &Verdict) -> String {
+    match verdict {
+        Verdict::Unknown => String::new(),
+        Verdict::Correct => format!(" {ANSI_GREEN}✔{ANSI_RESET}"),
+        Verdict::Incorrect { expected } => {
+            format!(" {ANSI_RED}✘ (expected {expected}){ANSI_RESET}")
+        }
+    }
+}
+
+fn print_stats(stats: &BenchStats) {
+    let BenchStats {
+        median,
+        min,
+        max,
+        p95,
+        outliers,
+        ..
+    } = stats;
+
+    outln!(
+        "        {ANSI_ITALIC}median {median:.1?} · p95 {p95:.1?} · min {min:.1?} · max {max:.1?} · {outliers} outliers{ANSI_RESET}"
+    );
+}
+
+/// Move the cursor back to the start of the line to overwrite an intermediate result.
+/// Intermediate results are not printed while the output is captured, see [`output::capture`].
+pub(crate) fn return_to_line_start() {
+    if !output::is_capturing() {
+        out!("\r");
+    }
+}
+
+fn print_result<T: Display>(result: &Result<&T, PartError>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
+        Ok(result) => {
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
+                    return_to_line_start();
+                    outln!("{str}");
+                    outln!("{result}");
                 }
             } else {
                 let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
+                    return_to_line_start();
+                    outln!("{str}");
                 }
             }
         }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+        Err(_) if is_intermediate_result => out!("{part}: ✖"),
+        Err(PartError::NotImplemented) => {
+            return_to_line_start();
+            outln!("{part}: ✖ not implemented");
+        }
+        Err(PartError::Failed(chain)) => {
+            let (error, causes) = chain.split_first().unzip();
+            return_to_line_start();
+            outln!(
+                "{part}: {ANSI_RED}✖ failed: {}{ANSI_RESET}{duration_str}",
+                error.map_or("", String::as_str)
+            );
+            for cause in causes.unwrap_or_default() {
+                outln!("        {ANSI_ITALIC}caused by: {cause}{ANSI_RESET}");
             }
         }
     }
 }
 
+/// Store the result as the known-correct answer for this part if `--record` was passed to `solve`.
+fn record_answer<T: Display>(result: &T, puzzle: PuzzleId, part: u8) {
+    let args: Vec<String> = env::args().collect();
+
+    if !args.contains(&"--record".into()) {
+        return;
+    }
+
+    // never replace a malformed answers file, it would lose every recorded answer.
+    let mut answers = match Answers::read_from_file(puzzle.year) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Not recording answer for part {part}: {e}");
+            return;
+        }
+    };
+    answers.set(puzzle.day, part, &result.to_string());
+
+    match answers.store_file(puzzle.year) {
+        Ok(()) => println!("Recorded answer for part {part}."),
+        Err(e) => eprintln!("Failed to record answer for part {part}: {e}"),
+    }
+}
+
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. aoc-cli is installed (or the session cookie is found, with the `native-client` feature).
+///  3. the submission log does not rule out the answer, see [`Submissions::check`].
+///
+/// The verdict is appended to the submission log; a correct answer is also recorded in the answers file.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<String, aoc_cli::AocCommandError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -155,11 +440,55 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    if let Err(e) = aoc_cli::check() {
+        eprintln!("{e}");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.to_string();
+    // without the log, known wrong answers can't be refused, and the next submission would replace it.
+    let mut submissions = match Submissions::read_from_file(puzzle.year) {
+        Ok(submissions) => submissions,
+        Err(e) => {
+            eprintln!("Refusing to submit {answer}: {e}");
+            return None;
+        }
+    };
+
+    if let Err(refusal) = submissions.check(puzzle.day, part, &answer) {
+        eprintln!("Refusing to submit {answer}: {refusal}");
+        return None;
+    }
+
+    println!("Submitting result...");
+    let result = aoc_cli::submit(puzzle, part, &answer);
+
+    let message = match &result {
+        Ok(message) => message.clone(),
+        Err(aoc_cli::AocCommandError::BadExitStatus(output)) => {
+            String::from_utf8_lossy(&output.stdout).to_string()
+        }
+        Err(_) => return Some(result),
+    };
+
+    let outcome = Outcome::parse(&message);
+    submissions.push(puzzle.day, part, &answer, outcome);
+
+    if let Err(e) = submissions.store_file(puzzle.year) {
+        eprintln!("Failed to log submission: {e}");
+    }
+
+    if outcome == Outcome::Correct {
+        match Answers::read_from_file(puzzle.year) {
+            Ok(mut answers) => {
+                answers.set(puzzle.day, part, &answer);
+                if let Err(e) = answers.store_file(puzzle.year) {
+                    eprintln!("Failed to record answer for part {part}: {e}");
+                }
+            }
+            Err(e) => eprintln!("Not recording answer for part {part}: {e}"),
+        }
+    }
+
+    Some(result)
 }
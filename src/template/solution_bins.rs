@@ -0,0 +1,48 @@
+/// The solution binaries in `src/bin`. Only depends on `std`, as `build.rs` includes this file to generate the
+/// registry of the same solutions that the `advent_of_code` binary builds.
+use std::{fs, path::Path};
+
+/// Lists the solutions that `build.rs` leaves out of the `registry` binary because they don't compile, e.g.
+/// `2024-06,2024-09`.
+pub const FAILED_TO_COMPILE_ENV: &str = "AOC_FAILED_TO_COMPILE";
+
+/// The names of all solution binaries in `bin_dir`, e.g. `2024-01`, in order.
+pub fn solution_bins(bin_dir: &Path) -> Vec<String> {
+    let mut bins: Vec<String> = fs::read_dir(bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| solution_bin(&entry.file_name().into_string().ok()?))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    bins.sort_unstable();
+    bins
+}
+
+/// The name of the solution binary of the file `name`, if it is named `YYYY-DD.rs`.
+fn solution_bin(name: &str) -> Option<String> {
+    let bin = name.strip_suffix(".rs")?;
+    let (year, day) = bin.split_once('-')?;
+    let is_solution = year.len() == 4
+        && day.len() == 2
+        && year.chars().chain(day.chars()).all(|c| c.is_ascii_digit());
+    is_solution.then(|| bin.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::solution_bin;
+
+    #[test]
+    fn finds_solution_bins() {
+        assert_eq!(solution_bin("2024-01.rs"), Some("2024-01".into()));
+        assert_eq!(solution_bin("registry.rs"), None);
+        assert_eq!(solution_bin("2024-1.rs"), None);
+        assert_eq!(solution_bin("2024-01.txt"), None);
+        assert_eq!(solution_bin("20x4-01.rs"), None);
+    }
+}
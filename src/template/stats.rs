@@ -0,0 +1,210 @@
+/// Summary statistics over the samples of a benchmark run.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// Statistics describing the execution time of a solution part.
+///
+/// All values except `min`, `max` and `outliers` are computed after outlier rejection.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BenchStats {
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+    /// Number of samples the statistics are based on.
+    pub samples: u128,
+    /// Number of samples that were rejected as outliers.
+    pub outliers: u128,
+}
+
+impl BenchStats {
+    /// Statistics for a part that was executed exactly once.
+    pub fn single(duration: Duration) -> Self {
+        Self {
+            mean: duration,
+            median: duration,
+            min: duration,
+            max: duration,
+            p95: duration,
+            stddev: Duration::ZERO,
+            samples: 1,
+            outliers: 0,
+        }
+    }
+
+    /// Compute statistics from a set of samples.
+    /// Samples outside of the Tukey fences (1.5 times the interquartile range) are rejected as outliers.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut sorted: Vec<u128> = samples.iter().map(Duration::as_nanos).collect();
+        sorted.sort_unstable();
+
+        let (Some(&min), Some(&max)) = (sorted.first(), sorted.last()) else {
+            return Self::default();
+        };
+
+        let q1 = percentile(&sorted, 25);
+        let q3 = percentile(&sorted, 75);
+        let fence = (q3 - q1) * 3 / 2;
+        let lower = q1.saturating_sub(fence);
+        let upper = q3 + fence;
+
+        let kept: Vec<u128> = sorted
+            .iter()
+            .copied()
+            .filter(|x| (lower..=upper).contains(x))
+            .collect();
+
+        let count = kept.len() as u128;
+        let mean = kept.iter().sum::<u128>() / count;
+
+        #[allow(clippy::cast_precision_loss)]
+        let variance = if count > 1 {
+            kept.iter()
+                .map(|x| (*x as f64 - mean as f64).powi(2))
+                .sum::<f64>()
+                / (count - 1) as f64
+        } else {
+            0_f64
+        };
+
+        Self {
+            mean: nanos(mean),
+            median: nanos(percentile(&kept, 50)),
+            min: nanos(min),
+            max: nanos(max),
+            p95: nanos(percentile(&kept, 95)),
+            stddev: Duration::from_secs_f64(variance.sqrt() / 1e9),
+            samples: count,
+            outliers: sorted.len() as u128 - count,
+        }
+    }
+}
+
+/// Nearest-rank percentile of a sorted, non-empty slice.
+fn percentile(sorted: &[u128], p: usize) -> u128 {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn nanos(value: u128) -> Duration {
+    Duration::from_nanos(value as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_precision_loss)]
+fn duration_to_json(duration: Duration) -> JsonValue {
+    JsonValue::Number(duration.as_nanos() as f64)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn duration_from_json(value: &JsonValue) -> Option<Duration> {
+    value.get::<f64>().map(|x| Duration::from_nanos(*x as u64))
+}
+
+impl From<&BenchStats> for HashMap<String, JsonValue> {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("nanos".into(), duration_to_json(value.mean));
+        map.insert("median_nanos".into(), duration_to_json(value.median));
+        map.insert("min_nanos".into(), duration_to_json(value.min));
+        map.insert("max_nanos".into(), duration_to_json(value.max));
+        map.insert("p95_nanos".into(), duration_to_json(value.p95));
+        map.insert("stddev_nanos".into(), duration_to_json(value.stddev));
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        map
+    }
+}
+
+impl TryFrom<&HashMap<String, JsonValue>> for BenchStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(json: &HashMap<String, JsonValue>) -> Result<Self, Self::Error> {
+        let duration = |key: &str| {
+            json.get(key)
+                .and_then(duration_from_json)
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        let count = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|x| *x as u128)
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        Ok(BenchStats {
+            mean: duration("nanos")?,
+            median: duration("median_nanos")?,
+            min: duration("min_nanos")?,
+            max: duration("max_nanos")?,
+            p95: duration("p95_nanos")?,
+            stddev: duration("stddev_nanos")?,
+            samples: count("samples")?,
+            outliers: count("outliers")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+    use tinyjson::JsonValue;
+
+    use super::BenchStats;
+
+    fn samples(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn computes_statistics() {
+        let stats = BenchStats::from_samples(&samples(&[10, 20, 30, 40, 50]));
+        assert_eq!(stats.mean, Duration::from_nanos(30));
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(50));
+        assert_eq!(stats.p95, Duration::from_nanos(50));
+        assert_eq!(stats.stddev.as_nanos(), 16);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = BenchStats::from_samples(&samples(&[100, 101, 99, 100, 102, 98, 100, 5000]));
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.samples, 7);
+        assert_eq!(stats.mean, Duration::from_nanos(100));
+        assert_eq!(stats.max, Duration::from_nanos(5000));
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = BenchStats::from_samples(&samples(&[42]));
+        assert_eq!(stats, BenchStats::single(Duration::from_nanos(42)));
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let stats = BenchStats::from_samples(&samples(&[10, 20, 30, 40, 50]));
+        let json = HashMap::<String, JsonValue>::from(&stats);
+        assert_eq!(BenchStats::try_from(&json).unwrap(), stats);
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        let stats = BenchStats::from_samples(&[]);
+        assert_eq!(stats.samples, 0);
+    }
+}
@@ -0,0 +1,119 @@
+/// Helpers for stress testing solutions with generated inputs of increasing size.
+use std::str::FromStr;
+
+/// Signature of the input generator a solution can register with `solution!(<day>, generate = <fn>)`.
+///
+/// `size` is a scale factor: `1` should produce an input about as large as the real one, `4` one that is four times
+/// larger. The same `seed` and `size` must produce the same input.
+pub type Generator = fn(u64, usize) -> String;
+
+/// A small, deterministic pseudo-random number generator for input generators.
+#[derive(Clone, Debug)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `0..bound`. Returns 0 if `bound` is 0.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            return 0;
+        }
+        self.next_u64() % bound
+    }
+}
+
+/// Parse a list of sizes like `1x,4x,16x`. The `x` suffix is optional.
+pub fn parse_sizes(s: &str) -> Result<Vec<usize>, String> {
+    s.split(',')
+        .map(|size| {
+            let size = size.trim();
+            usize::from_str(size.strip_suffix('x').unwrap_or(size))
+                .ok()
+                .filter(|size| *size > 0)
+                .ok_or(format!("invalid size `{size}`, expected e.g. `4x`."))
+        })
+        .collect()
+}
+
+/// Estimate `k` in `time ≈ c * n^k` from `(n, nanos)` samples with a least squares fit in log-log space.
+/// Returns `None` if there are fewer than two distinct input sizes.
+#[allow(clippy::cast_precision_loss)]
+pub fn estimate_exponent(samples: &[(usize, f64)]) -> Option<f64> {
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .filter(|(n, nanos)| *n > 0 && *nanos > 0.0)
+        .map(|(n, nanos)| ((*n as f64).ln(), nanos.ln()))
+        .collect();
+
+    let count = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / count;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / count;
+
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+
+    (variance > f64::EPSILON).then(|| covariance / variance)
+}
+
+/// The complexity class closest to an estimated exponent.
+pub fn complexity_class(exponent: f64) -> &'static str {
+    match exponent {
+        k if k < 0.5 => "O(1)",
+        k if k < 1.5 => "O(n)",
+        k if k < 2.5 => "O(n²)",
+        k if k < 3.5 => "O(n³)",
+        _ => "worse than O(n³)",
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{complexity_class, estimate_exponent, parse_sizes, SplitMix64};
+
+    #[test]
+    fn parses_sizes() {
+        assert_eq!(parse_sizes("1x,4x,16").unwrap(), vec![1, 4, 16]);
+        assert_eq!(parse_sizes("0x").is_err(), true);
+        assert_eq!(parse_sizes("big").is_err(), true);
+    }
+
+    #[test]
+    fn estimates_exponents() {
+        let quadratic: Vec<(usize, f64)> = [100, 400, 1600]
+            .iter()
+            .map(|n| (*n, 3.0 * (*n as f64).powi(2)))
+            .collect();
+        let exponent = estimate_exponent(&quadratic).unwrap();
+        assert_eq!((exponent - 2.0).abs() < 1e-9, true);
+        assert_eq!(complexity_class(exponent), "O(n²)");
+
+        assert_eq!(estimate_exponent(&[(100, 5.0)]), None);
+    }
+
+    #[test]
+    fn generates_deterministic_numbers() {
+        let mut a = SplitMix64::new(7);
+        let mut b = SplitMix64::new(7);
+        assert_eq!(a.next_u64(), b.next_u64());
+        assert_eq!(a.below(10) < 10, true);
+        assert_eq!(a.below(0), 0);
+    }
+}
@@ -0,0 +1,418 @@
+/// Local log of the answers submitted to adventofcode.com and the verdicts they received.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+fn get_path(year: Year) -> String {
+    format!("./data/{year}/submissions.json")
+}
+
+/// The verdict of the server on a submitted answer, as parsed from the output of aoc-cli.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in which direction.
+    Incorrect,
+    /// The answer was not checked because the previous submission was too recent.
+    RateLimited,
+    /// The answer was not checked because the part has already been solved.
+    AlreadySolved,
+    /// The output could not be parsed.
+    Unknown,
+}
+
+impl Outcome {
+    /// Parse the verdict from the (stdout) output of `aoc submit`.
+    pub fn parse(output: &str) -> Self {
+        let output = output.to_lowercase();
+
+        if output.contains("that's the right answer") {
+            Outcome::Correct
+        } else if output.contains("too high") {
+            Outcome::TooHigh
+        } else if output.contains("too low") {
+            Outcome::TooLow
+        } else if output.contains("not the right answer") {
+            Outcome::Incorrect
+        } else if output.contains("answer too recently") {
+            Outcome::RateLimited
+        } else if output.contains("solving the right level") {
+            Outcome::AlreadySolved
+        } else {
+            Outcome::Unknown
+        }
+    }
+
+    /// Whether the answer was checked and found to be wrong.
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Outcome::TooHigh | Outcome::TooLow | Outcome::Incorrect
+        )
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too_high",
+            Outcome::TooLow => "too_low",
+            Outcome::Incorrect => "incorrect",
+            Outcome::RateLimited => "rate_limited",
+            Outcome::AlreadySolved => "already_solved",
+            Outcome::Unknown => "unknown",
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Outcome::Correct,
+            Outcome::TooHigh,
+            Outcome::TooLow,
+            Outcome::Incorrect,
+            Outcome::RateLimited,
+            Outcome::AlreadySolved,
+            Outcome::Unknown,
+        ]
+        .into_iter()
+        .find(|x| x.as_str() == s)
+        .ok_or(format!("Unknown submission outcome: {s}"))
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+}
+
+/// The reason an answer is not submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part has already been solved with the contained answer.
+    AlreadyCorrect(String),
+    /// The answer has been submitted before and was wrong.
+    KnownWrong(Outcome),
+    /// The answer is at least as high as a previous answer that was too high.
+    AboveUpperBound(String),
+    /// The answer is at most as low as a previous answer that was too low.
+    BelowLowerBound(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadyCorrect(answer) => {
+                write!(f, "this part was already solved with {answer}.")
+            }
+            Refusal::KnownWrong(outcome) => {
+                write!(
+                    f,
+                    "this answer was submitted before and was {}.",
+                    outcome.as_str().replace('_', " ")
+                )
+            }
+            Refusal::AboveUpperBound(bound) => {
+                write!(
+                    f,
+                    "the answer must be lower than {bound}, which was too high."
+                )
+            }
+            Refusal::BelowLowerBound(bound) => {
+                write!(
+                    f,
+                    "the answer must be higher than {bound}, which was too low."
+                )
+            }
+        }
+    }
+}
+
+/// The log of all submissions. Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate the log to the JSON file of a year.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(get_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the log from the JSON file of a year. If not present, returns an empty log.
+    /// Fails if the file can't be read or is malformed, so that the history is never replaced by a new log.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = get_path(year);
+        match fs::read_to_string(&path) {
+            Ok(json) => {
+                Submissions::try_from(json).map_err(|e| format!("\"{path}\" is malformed: {e}"))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("could not read \"{path}\": {e}")),
+        }
+    }
+
+    /// Add a submission to the log.
+    pub fn push(&mut self, day: Day, part: u8, answer: &str, outcome: Outcome) {
+        self.data.push(Submission {
+            day,
+            part,
+            answer: answer.into(),
+            outcome,
+        });
+    }
+
+    /// Check whether `answer` may be submitted for a part, based on earlier submissions.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Refusal> {
+        let submissions: Vec<&Submission> = self
+            .data
+            .iter()
+            .filter(|s| s.day == day && s.part == part)
+            .collect();
+
+        if let Some(correct) = submissions.iter().find(|s| s.outcome == Outcome::Correct) {
+            return Err(Refusal::AlreadyCorrect(correct.answer.clone()));
+        }
+
+        if let Some(wrong) = submissions
+            .iter()
+            .find(|s| s.answer == answer && s.outcome.is_wrong())
+        {
+            return Err(Refusal::KnownWrong(wrong.outcome));
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |outcome: Outcome| {
+            submissions
+                .iter()
+                .filter(move |s| s.outcome == outcome)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(upper) = bound(Outcome::TooHigh).min() {
+            if value >= upper {
+                return Err(Refusal::AboveUpperBound(upper.to_string()));
+            }
+        }
+
+        if let Some(lower) = bound(Outcome::TooLow).max() {
+            if value <= lower {
+                return Err(Refusal::BelowLowerBound(lower.to_string()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(value.outcome.as_str().into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let outcome = json
+            .get("outcome")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.outcome to be a string.")?
+            .parse::<Outcome>()?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            outcome,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Outcome, Refusal, Submissions};
+    use crate::day;
+
+    fn get_mock_submissions() -> Submissions {
+        let mut submissions = Submissions::default();
+        submissions.push(day!(13), 2, "875318608908", Outcome::TooLow);
+        submissions.push(day!(13), 2, "95000000000000", Outcome::TooHigh);
+        submissions.push(day!(13), 2, "82041245827082", Outcome::Incorrect);
+        submissions.push(day!(13), 1, "31761", Outcome::Correct);
+        submissions
+    }
+
+    #[test]
+    fn parses_outcomes() {
+        assert_eq!(
+            Outcome::parse("That's the right answer! You are one gold star closer."),
+            Outcome::Correct
+        );
+        assert_eq!(
+            Outcome::parse("That's not the right answer; your answer is too high."),
+            Outcome::TooHigh
+        );
+        assert_eq!(
+            Outcome::parse("That's not the right answer; your answer is too low."),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            Outcome::parse("That's not the right answer. If you're stuck, ..."),
+            Outcome::Incorrect
+        );
+        assert_eq!(
+            Outcome::parse("You gave an answer too recently; you have to wait."),
+            Outcome::RateLimited
+        );
+        assert_eq!(
+            Outcome::parse("You don't seem to be solving the right level."),
+            Outcome::AlreadySolved
+        );
+        assert_eq!(Outcome::parse(""), Outcome::Unknown);
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(13), 1, "1"),
+            Err(Refusal::AlreadyCorrect("31761".into()))
+        );
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(13), 2, "82041245827082"),
+            Err(Refusal::KnownWrong(Outcome::Incorrect))
+        );
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(13), 2, "100000000000000"),
+            Err(Refusal::AboveUpperBound("95000000000000".into()))
+        );
+        assert_eq!(
+            submissions.check(day!(13), 2, "42"),
+            Err(Refusal::BelowLowerBound("875318608908".into()))
+        );
+        assert_eq!(submissions.check(day!(13), 2, "90798500745591"), Ok(()));
+    }
+
+    #[test]
+    fn allows_unrelated_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(submissions.check(day!(12), 2, "42"), Ok(()));
+        assert_eq!(submissions.check(day!(13), 2, "not a number"), Ok(()));
+    }
+
+    #[test]
+    fn round_trips_submissions() {
+        let submissions = get_mock_submissions();
+        let json = tinyjson::JsonValue::from(submissions.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(Submissions::try_from(json).unwrap(), submissions);
+    }
+}
@@ -1,17 +1,32 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+    time::Duration,
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{BenchStats, Day, PartRecord, Year};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+fn get_path(year: Year) -> String {
+    format!("./data/{year}/timings.json")
+}
+
+/// Version of the `timings.json` format written by [`Timings::store_file`].
+///  1. (unversioned) parts are formatted strings like `"74.13ns"`, optionally accompanied by `part_N_stats`.
+///  2. parts are [`BenchStats`] objects.
+const TIMINGS_VERSION: u8 = 2;
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub part_1: Option<BenchStats>,
+    pub part_2: Option<BenchStats>,
     pub total_nanos: f64,
+    /// Parts that ran into their timeout.
+    pub timed_out: Vec<u8>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -21,20 +36,109 @@ pub struct Timings {
     pub data: Vec<Timing>,
 }
 
+impl Timing {
+    /// Collect the timings of all answered parts of a day.
+    pub fn from_records(day: Day, records: &[PartRecord]) -> Self {
+        let mut timing = Timing {
+            day,
+            part_1: None,
+            part_2: None,
+            total_nanos: 0_f64,
+            timed_out: vec![],
+        };
+
+        timing.timed_out = records
+            .iter()
+            .filter(|r| r.timed_out)
+            .map(|r| r.part)
+            .collect();
+
+        for record in records.iter().filter(|r| r.answer.is_some()) {
+            match record.part {
+                1 => timing.part_1 = Some(record.stats),
+                2 => timing.part_2 = Some(record.stats),
+                _ => continue,
+            }
+
+            #[allow(clippy::cast_precision_loss)]
+            {
+                timing.total_nanos += record.stats.mean.as_nanos() as f64;
+            }
+        }
+
+        timing
+    }
+
+    /// Mean execution time of a part, if it has been benched.
+    pub fn part_duration(&self, part: u8) -> Option<Duration> {
+        match part {
+            1 => self.part_1.map(|x| x.mean),
+            2 => self.part_2.map(|x| x.mean),
+            _ => None,
+        }
+    }
+
+    pub fn is_timed_out(&self, part: u8) -> bool {
+        self.timed_out.contains(&part)
+    }
+}
+
+/// Parse a duration formatted with `{:?}`, e.g. `74.13ns` or `1.5ms`.
+fn parse_duration(s: &str) -> Option<Duration> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let (value, unit_nanos) = [
+        ("ns", 1_f64),
+        ("µs", 1_000_f64),
+        ("ms", 1_000_000_f64),
+        ("s", 1_000_000_000_f64),
+    ]
+    .iter()
+    .find_map(|(postfix, factor)| Some((s.trim().strip_suffix(postfix)?, factor)))?;
+
+    let nanos = value.parse::<f64>().ok()? * unit_nanos;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    (nanos >= 0_f64).then(|| Duration::from_nanos(nanos.round() as u64))
+}
+
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Group records by day and collect the timings of each day.
+    pub fn from_records(records: &[PartRecord]) -> Self {
+        let mut days: Vec<Day> = records.iter().map(|r| r.day).collect();
+        days.sort_unstable();
+        days.dedup();
+
+        let data = days
+            .into_iter()
+            .map(|day| {
+                let day_records: Vec<PartRecord> =
+                    records.iter().filter(|r| r.day == day).cloned().collect();
+                Timing::from_records(day, &day_records)
+            })
+            .collect();
+
+        Timings { data }
+    }
+
+    /// Dehydrate timings to the JSON file of a year.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(get_path(year))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Rehydrate timings from the JSON file of a year. If not present, returns empty timings.
+    /// Fails if the file can't be read, is malformed or has an unsupported version, so that it is never replaced by
+    /// empty timings.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = get_path(year);
+        match fs::read_to_string(&path) {
+            Ok(json) => {
+                Timings::try_from(json).map_err(|e| format!("\"{path}\" is malformed: {e}"))
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("could not read \"{path}\": {e}")),
+        }
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
@@ -51,7 +155,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -73,6 +177,10 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(TIMINGS_VERSION)),
+        );
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -88,18 +196,34 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        // files written before the format was versioned are version 1.
+        let version = match json.get("version") {
+            None => 1_f64,
+            Some(v) => *v
+                .get::<f64>()
+                .ok_or("expected `json.version` to be a number.")?,
+        };
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        let parse_timing: fn(&JsonValue) -> Result<Timing, String> = match version {
+            1_f64 => migrate_timing_v1,
+            2_f64 => |v| Timing::try_from(v),
+            v => return Err(format!("unsupported timings version `{v}`.")),
+        };
+
         Ok(Timings {
             data: json_data
                 .iter()
-                .map(Timing::try_from)
+                .map(parse_timing)
                 .collect::<Result<_, _>>()?,
         })
     }
@@ -113,30 +237,69 @@ impl From<&Timing> for JsonValue {
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
-
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "timed_out".into(),
+            JsonValue::Array(
+                value
+                    .timed_out
+                    .iter()
+                    .map(|part| JsonValue::Number(f64::from(*part)))
+                    .collect(),
+            ),
         );
 
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        for (key, stats) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match stats {
+                    Some(x) => JsonValue::Object(x.into()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
 
         JsonValue::Object(map)
     }
 }
 
+fn parse_day(json: &HashMap<String, JsonValue>) -> Result<Day, String> {
+    json.get("day")
+        .and_then(|v| v.get::<String>())
+        .and_then(|day| Day::from_str(day).ok())
+        .ok_or("Expected timing.day to be a Day struct.".into())
+}
+
+fn parse_total_nanos(json: &HashMap<String, JsonValue>) -> Result<f64, String> {
+    json.get("total_nanos")
+        .and_then(|v| v.get::<f64>().copied())
+        .ok_or("Expected timing.total_nanos to be a number.".into())
+}
+
+/// Timings stored before timeouts were introduced have no `timed_out` key.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse_timed_out(json: &HashMap<String, JsonValue>) -> Result<Vec<u8>, String> {
+    match json.get("timed_out") {
+        None => Ok(vec![]),
+        Some(JsonValue::Array(parts)) => parts
+            .iter()
+            .map(|part| {
+                part.get::<f64>()
+                    .map(|part| *part as u8)
+                    .ok_or("Expected timing.timed_out to be an array of numbers.".into())
+            })
+            .collect(),
+        _ => Err("Expected timing.timed_out to be an array.".into()),
+    }
+}
+
+fn parse_stats(json: &HashMap<String, JsonValue>, key: &str) -> Result<Option<BenchStats>, String> {
+    match json.get(key) {
+        Some(JsonValue::Null) => Ok(None),
+        Some(JsonValue::Object(x)) => BenchStats::try_from(x).map(Some),
+        _ => Err(format!("Expected timing.{key} to be null or an object.")),
+    }
+}
+
 impl TryFrom<&JsonValue> for Timing {
     type Error = String;
 
@@ -145,41 +308,59 @@ impl TryFrom<&JsonValue> for Timing {
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
-        let day = json
-            .get("day")
-            .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
-            .ok_or("Expected timing.day to be a Day struct.")?;
-
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
-
-        let total_nanos = json
-            .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
-            .ok_or("Expected timing.total_nanos to be a number.")?;
-
         Ok(Timing {
-            day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
-            total_nanos,
+            day: parse_day(json)?,
+            part_1: parse_stats(json, "part_1")?,
+            part_2: parse_stats(json, "part_2")?,
+            total_nanos: parse_total_nanos(json)?,
+            timed_out: parse_timed_out(json)?,
         })
     }
 }
 
+/// Read a timing in the version 1 format, where parts are formatted durations.
+/// Stats that were stored next to the formatted parts are kept, otherwise the formatted duration is
+/// used for all statistics and the sample count is left at 0 as it was not recorded.
+fn migrate_timing_v1(value: &JsonValue) -> Result<Timing, String> {
+    let json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected timing to be a JSON object.")?;
+
+    let part = |key: &str| -> Result<Option<BenchStats>, String> {
+        if let Some(JsonValue::Object(stats)) = json.get(&format!("{key}_stats")) {
+            return BenchStats::try_from(stats).map(Some);
+        }
+
+        match json.get(key) {
+            Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(x)) => parse_duration(x)
+                .map(|duration| {
+                    Some(BenchStats {
+                        samples: 0,
+                        ..BenchStats::single(duration)
+                    })
+                })
+                .ok_or(format!("Expected timing.{key} to be a duration.")),
+            _ => Err(format!("Expected timing.{key} to be null or string.")),
+        }
+    };
+
+    Ok(Timing {
+        day: parse_day(json)?,
+        part_1: part("part_1")?,
+        part_2: part("part_2")?,
+        total_nanos: parse_total_nanos(json)?,
+        timed_out: vec![],
+    })
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use std::time::Duration;
+
+    use crate::{day, template::BenchStats};
 
     use super::{Timing, Timings};
 
@@ -188,39 +369,86 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(BenchStats::single(Duration::from_millis(10))),
+                    part_2: Some(BenchStats::single(Duration::from_millis(20))),
                     total_nanos: 3e+10,
+                    timed_out: vec![],
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(BenchStats::single(Duration::from_millis(30))),
+                    part_2: Some(BenchStats::single(Duration::from_millis(40))),
                     total_nanos: 7e+10,
+                    timed_out: vec![],
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    part_1: Some(BenchStats::single(Duration::from_millis(40))),
                     part_2: None,
                     total_nanos: 4e+10,
+                    timed_out: vec![],
                 },
             ],
         }
     }
 
     mod deserialization {
+        use std::time::Duration;
+
         use crate::{day, template::timings::Timings};
 
         #[test]
         fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": { "nanos": 1000000, "median_nanos": 990000, "min_nanos": 900000, "max_nanos": 1200000, "p95_nanos": 1100000, "stddev_nanos": 5000, "samples": 1000, "outliers": 3 }, "part_2": null, "total_nanos": 1000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            let stats = timing.part_1.unwrap();
+            assert_eq!(stats.mean, Duration::from_millis(1));
+            assert_eq!(stats.median.as_nanos(), 990_000);
+            assert_eq!(stats.samples, 1000);
+            assert_eq!(stats.outliers, 3);
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.total_nanos, 1_000_000_f64);
+        }
+
+        #[test]
+        fn migrates_version_1_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.5ms", "part_2": null, "total_nanos": 1500000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1.unwrap();
+            assert_eq!(stats.mean, Duration::from_micros(1500));
+            assert_eq!(stats.samples, 0);
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+        }
+
+        #[test]
+        fn migrates_version_1_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "nanos": 1000000, "median_nanos": 990000, "min_nanos": 900000, "max_nanos": 1200000, "p95_nanos": 1100000, "stddev_nanos": 5000, "samples": 1000, "outliers": 3 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1.unwrap();
+            assert_eq!(stats.median.as_nanos(), 990_000);
+            assert_eq!(stats.outliers, 3);
+            assert_eq!(timing.part_2, None);
+        }
+
+        #[test]
+        fn parses_formatted_durations() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "74.13ns", "part_2": "2s", "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_duration(1), Some(Duration::from_nanos(74)));
+            assert_eq!(timing.part_duration(2), Some(Duration::from_secs(2)));
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_unknown_versions() {
+            let json = r#"{ "version": 99, "data": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
         }
 
         #[test]
@@ -247,6 +475,7 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::Timings;
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -266,12 +495,27 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn round_trips_timings() {
+            let timings = get_mock_timings();
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data.len(), 3);
+            assert_eq!(parsed.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(parsed.data[2].part_2, None);
+        }
     }
 
     mod is_day_complete {
+        use std::time::Duration;
+
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                timings::{Timing, Timings},
+                BenchStats,
+            },
         };
 
         #[test]
@@ -279,9 +523,10 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1: Some(BenchStats::single(Duration::from_millis(1))),
+                    part_2: Some(BenchStats::single(Duration::from_millis(2))),
                     total_nanos: 3_000_000_000_f64,
+                    timed_out: vec![],
                 }],
             };
 
@@ -293,9 +538,10 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    part_1: Some(BenchStats::single(Duration::from_millis(1))),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    timed_out: vec![],
                 }],
             };
 
@@ -310,6 +556,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    timed_out: vec![],
                 }],
             };
 
@@ -317,6 +564,79 @@ mod tests {
         }
     }
 
+    mod from_records {
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::{
+                timings::{Timing, Timings},
+                BenchStats, Day, PartRecord,
+            },
+            year,
+        };
+
+        fn record(part: u8, answer: Option<&str>, nanos: u64) -> PartRecord {
+            day_record(day!(1), part, answer, nanos)
+        }
+
+        fn day_record(day: Day, part: u8, answer: Option<&str>, nanos: u64) -> PartRecord {
+            PartRecord {
+                year: year!(2024),
+                day,
+                part,
+                answer: answer.map(Into::into),
+                stats: BenchStats::single(Duration::from_nanos(nanos)),
+                timed_out: false,
+                error: None,
+            }
+        }
+
+        #[test]
+        fn collects_answered_parts() {
+            let timing = Timing::from_records(
+                day!(1),
+                &[
+                    record(1, Some("42"), 1_500),
+                    record(2, Some("x"), 2_000_000),
+                ],
+            );
+            assert_eq!(timing.part_duration(1), Some(Duration::from_nanos(1_500)));
+            assert_eq!(timing.part_duration(2), Some(Duration::from_millis(2)));
+            assert_eq!(timing.part_1.unwrap().samples, 1);
+            assert_eq!(timing.total_nanos, 2_001_500_f64);
+        }
+
+        #[test]
+        fn skips_unanswered_parts() {
+            let timing = Timing::from_records(day!(1), &[record(1, None, 1_500)]);
+            assert_eq!(timing.part_1, None);
+            assert_eq!(timing.total_nanos, 0_f64);
+        }
+
+        #[test]
+        fn collects_timed_out_parts() {
+            let mut timed_out = record(2, None, 5_000_000_000);
+            timed_out.timed_out = true;
+            let timing = Timing::from_records(day!(1), &[record(1, Some("42"), 1_500), timed_out]);
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.is_timed_out(2), true);
+            assert_eq!(timing.is_timed_out(1), false);
+        }
+
+        #[test]
+        fn groups_records_by_day() {
+            let timings = Timings::from_records(&[
+                day_record(day!(2), 1, Some("a"), 10),
+                day_record(day!(1), 1, Some("b"), 20),
+                day_record(day!(2), 2, Some("c"), 30),
+            ]);
+            assert_eq!(timings.data.len(), 2);
+            assert_eq!(timings.data[0].day, day!(1));
+            assert_eq!(timings.data[1].total_nanos, 40_f64);
+        }
+    }
+
     mod merge {
         use crate::{
             day,
@@ -334,6 +654,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    timed_out: vec![],
                 }],
             };
             let merged = timings.merge(&other);
@@ -354,6 +675,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    timed_out: vec![],
                 }],
             };
             let merged = timings.merge(&other);
@@ -0,0 +1,172 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(feature = "today")]
+use chrono::{Datelike, Utc};
+
+/// The first year of advent of code.
+const FIRST_YEAR: u16 = 2015;
+
+/// A valid year of advent of code (i.e. an integer from 2015 onwards).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.to_string(), "2024")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(FIRST_YEAR..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the year configured by the `AOC_YEAR` environment variable, if it is set and valid.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// Returns the year of a solution file named `<year>-<day>.rs`, e.g. `src/bin/2024-01.rs`.
+    ///
+    /// # Panics
+    /// If the file name does not follow this pattern. When evaluated in a const context, this is a compile error.
+    pub const fn from_solution_path(path: &str) -> Self {
+        let bytes = path.as_bytes();
+
+        // find the start of the file name.
+        let mut start = bytes.len();
+        while start > 0 && bytes[start - 1] != b'/' && bytes[start - 1] != b'\\' {
+            start -= 1;
+        }
+
+        let is_valid = bytes.len() - start == "YYYY-DD.rs".len() && bytes[start + 4] == b'-';
+        assert!(
+            is_valid,
+            "solution files must be named `<year>-<day>.rs`, e.g. `src/bin/2024-01.rs`"
+        );
+
+        let mut year: u16 = 0;
+        let mut i = start;
+        while i < start + 4 {
+            assert!(
+                bytes[i].is_ascii_digit(),
+                "solution files must be named `<year>-<day>.rs`, e.g. `src/bin/2024-01.rs`"
+            );
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        assert!(year >= FIRST_YEAR, "invalid year in solution file name");
+        Self(year)
+    }
+}
+
+#[cfg(feature = "today")]
+impl Year {
+    /// Returns the current year.
+    pub fn today() -> Option<Self> {
+        Self::new(u16::try_from(Utc::now().year()).ok()?)
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year from 2015 onwards")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015 && $year <= 9999,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a value from 2015 onwards"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2024".parse::<Year>().unwrap(), Year(2024));
+        assert_eq!("2014".parse::<Year>().is_err(), true);
+        assert_eq!("24".parse::<Year>().is_err(), true);
+    }
+
+    #[test]
+    fn reads_year_from_solution_path() {
+        assert_eq!(Year::from_solution_path("src/bin/2023-07.rs"), Year(2023));
+        assert_eq!(
+            Year::from_solution_path("/home/aoc/src/bin/2024-25.rs"),
+            Year(2024)
+        );
+        assert_eq!(
+            Year::from_solution_path(r"C:\aoc\src\bin\2016-01.rs"),
+            Year(2016)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_solution_paths_without_year() {
+        Year::from_solution_path("src/bin/07.rs");
+    }
+}